@@ -1,7 +1,7 @@
-use std::time::{Duration, SystemTime};
-
 use bytemuck::{Pod, TransparentWrapper, Zeroable};
 
+use crate::{error::ValidationError, Timestamp};
+
 mod balance;
 mod filter;
 
@@ -23,6 +23,14 @@ impl Account {
             .with_code(code)
     }
 
+    /// Same as [`Account::new()`], but fails with a [`ValidationError`] instead of panicking.
+    pub fn try_new(id: u128, ledger: u32, code: u16) -> Result<Self, ValidationError> {
+        Account(Raw::zeroed())
+            .try_with_id(id)?
+            .try_with_ledger(ledger)?
+            .try_with_code(code)
+    }
+
     pub const fn from_raw(raw: Raw) -> Self {
         Account(raw)
     }
@@ -41,19 +49,33 @@ impl Account {
     }
     #[track_caller]
     pub fn set_id(&mut self, id: u128) {
-        assert_ne!(id, 0, "account id must not be zero");
-        assert_ne!(
-            id,
-            u128::MAX,
-            "account id must not be `2^128 - 1` (the highest 128-bit unsigned integer)"
-        );
+        if let Err(e) = self.try_set_id(id) {
+            panic!("{e}");
+        }
+    }
+    pub fn try_set_id(&mut self, id: u128) -> Result<(), ValidationError> {
+        if id == 0 {
+            return Err(ValidationError::Zero {
+                field: "account id",
+            });
+        }
+        if id == u128::MAX {
+            return Err(ValidationError::Max {
+                field: "account id",
+            });
+        }
         self.0.id = id;
+        Ok(())
     }
     #[track_caller]
     pub fn with_id(mut self, id: u128) -> Self {
         self.set_id(id);
         self
     }
+    pub fn try_with_id(mut self, id: u128) -> Result<Self, ValidationError> {
+        self.try_set_id(id)?;
+        Ok(self)
+    }
 
     pub const fn user_data_128(&self) -> u128 {
         self.0.user_data_128
@@ -93,28 +115,56 @@ impl Account {
     }
     #[track_caller]
     pub fn set_ledger(&mut self, ledger: u32) {
-        assert_ne!(ledger, 0, "account ledger must not be zero");
+        if let Err(e) = self.try_set_ledger(ledger) {
+            panic!("{e}");
+        }
+    }
+    pub fn try_set_ledger(&mut self, ledger: u32) -> Result<(), ValidationError> {
+        if ledger == 0 {
+            return Err(ValidationError::Zero {
+                field: "account ledger",
+            });
+        }
         self.0.ledger = ledger;
+        Ok(())
     }
     #[track_caller]
     pub fn with_ledger(mut self, ledger: u32) -> Self {
         self.set_ledger(ledger);
         self
     }
+    pub fn try_with_ledger(mut self, ledger: u32) -> Result<Self, ValidationError> {
+        self.try_set_ledger(ledger)?;
+        Ok(self)
+    }
 
     pub const fn code(&self) -> u16 {
         self.0.code
     }
     #[track_caller]
     pub fn set_code(&mut self, code: u16) {
-        assert_ne!(code, 0, "account code must not be zero");
+        if let Err(e) = self.try_set_code(code) {
+            panic!("{e}");
+        }
+    }
+    pub fn try_set_code(&mut self, code: u16) -> Result<(), ValidationError> {
+        if code == 0 {
+            return Err(ValidationError::Zero {
+                field: "account code",
+            });
+        }
         self.0.code = code;
+        Ok(())
     }
     #[track_caller]
     pub fn with_code(mut self, code: u16) -> Self {
         self.set_code(code);
         self
     }
+    pub fn try_with_code(mut self, code: u16) -> Result<Self, ValidationError> {
+        self.try_set_code(code)?;
+        Ok(self)
+    }
 
     pub const fn flags(&self) -> Flags {
         Flags::from_bits_retain(self.0.flags)
@@ -140,8 +190,32 @@ impl Account {
         self.0.credits_posted
     }
 
-    pub fn timestamp(&self) -> SystemTime {
-        SystemTime::UNIX_EPOCH + Duration::from_nanos(self.0.timestamp)
+    pub const fn timestamp(&self) -> Option<Timestamp> {
+        Timestamp::from_nanos(self.0.timestamp)
+    }
+    pub fn set_timestamp(&mut self, timestamp: Option<Timestamp>) {
+        self.0.timestamp = Timestamp::into_raw(timestamp);
+    }
+    pub const fn with_timestamp(mut self, timestamp: Option<Timestamp>) -> Self {
+        self.0.timestamp = Timestamp::into_raw(timestamp);
+        self
+    }
+
+    /// Compares this [`Account`] with the `other` one by their content only, ignoring the fields
+    /// populated by the cluster (its balances and timestamp).
+    ///
+    /// Allows checking whether an [`Account`] looked up from the cluster is the one submitted for
+    /// creation.
+    pub fn content_eq(&self, other: &Self) -> bool {
+        let (mut this, mut other) = (self.0, other.0);
+        for raw in [&mut this, &mut other] {
+            raw.debits_pending = 0;
+            raw.debits_posted = 0;
+            raw.credits_pending = 0;
+            raw.credits_posted = 0;
+            raw.timestamp = 0;
+        }
+        Self(this) == Self(other)
     }
 }
 
@@ -164,6 +238,8 @@ impl std::fmt::Debug for Account {
     }
 }
 
+impl_eq_by_bytes!(Account);
+
 impl From<Raw> for Account {
     fn from(value: Raw) -> Self {
         Account(value)
@@ -174,3 +250,79 @@ impl From<Account> for Raw {
         value.0
     }
 }
+
+#[cfg(test)]
+mod account_spec {
+    use std::collections::HashSet;
+
+    use crate::{error::ValidationError, Timestamp};
+
+    use super::Account;
+
+    #[test]
+    fn try_new_validates_fields() {
+        assert_eq!(
+            Account::try_new(0, 1, 1).err(),
+            Some(ValidationError::Zero {
+                field: "account id",
+            }),
+        );
+        assert_eq!(
+            Account::try_new(u128::MAX, 1, 1).err(),
+            Some(ValidationError::Max {
+                field: "account id",
+            }),
+        );
+        assert_eq!(
+            Account::try_new(1, 0, 1).err(),
+            Some(ValidationError::Zero {
+                field: "account ledger",
+            }),
+        );
+        assert_eq!(
+            Account::try_new(1, 1, 0).err(),
+            Some(ValidationError::Zero {
+                field: "account code",
+            }),
+        );
+        assert_eq!(Account::try_new(1, 2, 3).map(|a| a.ledger()), Ok(2));
+    }
+
+    #[test]
+    fn failed_try_set_keeps_value() {
+        let mut account = Account::new(1, 1, 1);
+
+        assert!(account.try_set_id(0).is_err());
+        assert_eq!(account.id(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid account id: must not be zero")]
+    fn set_id_panics_on_zero() {
+        Account::new(1, 1, 1).set_id(0);
+    }
+
+    #[test]
+    fn compares_and_hashes_all_fields() {
+        let account = Account::new(1, 2, 3).with_user_data_64(4);
+
+        assert_eq!(account, Account::new(1, 2, 3).with_user_data_64(4));
+        assert_ne!(account, account.with_user_data_64(5));
+        assert_ne!(account, account.with_timestamp(Timestamp::from_nanos(6)));
+
+        let set = HashSet::from([account, account, account.with_user_data_64(5)]);
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn content_eq_ignores_cluster_populated_fields() {
+        let submitted = Account::new(1, 2, 3).with_user_data_64(4);
+        let mut created = submitted.with_timestamp(Timestamp::from_nanos(5));
+        created.as_raw_mut().debits_posted = 6;
+        created.as_raw_mut().credits_pending = 7;
+
+        assert_ne!(submitted, created);
+        assert!(submitted.content_eq(&created));
+        assert!(!submitted.content_eq(&created.with_user_data_64(8)));
+    }
+}
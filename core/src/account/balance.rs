@@ -1,7 +1,7 @@
-use std::time::{Duration, SystemTime};
-
 use bytemuck::{Pod, TransparentWrapper, Zeroable};
 
+use crate::Timestamp;
+
 pub use sys::tb_account_balance_t as Raw;
 
 #[repr(transparent)]
@@ -66,8 +66,15 @@ impl Balance {
         self
     }
 
-    pub fn timestamp(&self) -> SystemTime {
-        SystemTime::UNIX_EPOCH + Duration::from_nanos(self.0.timestamp)
+    pub const fn timestamp(&self) -> Option<Timestamp> {
+        Timestamp::from_nanos(self.0.timestamp)
+    }
+    pub fn set_timestamp(&mut self, timestamp: Option<Timestamp>) {
+        self.0.timestamp = Timestamp::into_raw(timestamp);
+    }
+    pub const fn with_timestamp(mut self, timestamp: Option<Timestamp>) -> Self {
+        self.0.timestamp = Timestamp::into_raw(timestamp);
+        self
     }
 }
 
@@ -77,12 +84,14 @@ impl std::fmt::Debug for Balance {
             .field("debits_pending", &self.0.debits_pending)
             .field("debits_posted", &self.0.debits_posted)
             .field("credits_pending", &self.0.credits_pending)
-            .field("credits_pending", &self.0.credits_pending)
+            .field("credits_posted", &self.0.credits_posted)
             .field("timestamp", &self.0.timestamp)
             .finish_non_exhaustive()
     }
 }
 
+impl_eq_by_bytes!(Balance);
+
 impl From<Raw> for Balance {
     fn from(value: Raw) -> Self {
         Balance(value)
@@ -1,7 +1,7 @@
-use std::time::{Duration, SystemTime};
-
 use bytemuck::{Pod, TransparentWrapper, Zeroable};
 
+use crate::{error::ValidationError, Timestamp};
+
 pub use sys::generated_safe::AccountFilterFlags as Flags;
 pub use sys::tb_account_filter_t as Raw;
 
@@ -29,6 +29,13 @@ impl Filter {
             .with_limit(limit)
     }
 
+    /// Same as [`Filter::new()`], but fails with a [`ValidationError`] instead of panicking.
+    pub fn try_new(account_id: u128, limit: u32) -> Result<Self, ValidationError> {
+        Filter(Raw::zeroed())
+            .try_with_account_id(account_id)?
+            .try_with_limit(limit)
+    }
+
     pub const fn from_raw(raw: Raw) -> Self {
         Filter(raw)
     }
@@ -45,60 +52,78 @@ impl Filter {
     pub const fn account_id(&self) -> u128 {
         self.0.account_id
     }
+    #[track_caller]
     pub fn set_account_id(&mut self, account_id: u128) {
-        assert_ne!(account_id, u128::MAX, "account_id must not be `2^128 - 1`");
+        if let Err(e) = self.try_set_account_id(account_id) {
+            panic!("{e}");
+        }
+    }
+    pub fn try_set_account_id(&mut self, account_id: u128) -> Result<(), ValidationError> {
+        if account_id == u128::MAX {
+            return Err(ValidationError::Max {
+                field: "account id",
+            });
+        }
         self.0.account_id = account_id;
+        Ok(())
     }
-    pub const fn with_account_id(mut self, account_id: u128) -> Self {
-        self.0.account_id = account_id;
+    #[track_caller]
+    pub fn with_account_id(mut self, account_id: u128) -> Self {
+        self.set_account_id(account_id);
         self
     }
+    pub fn try_with_account_id(mut self, account_id: u128) -> Result<Self, ValidationError> {
+        self.try_set_account_id(account_id)?;
+        Ok(self)
+    }
 
-    pub fn timestamp_min(&self) -> SystemTime {
-        SystemTime::UNIX_EPOCH + Duration::from_nanos(self.0.timestamp_min)
+    pub const fn timestamp_min(&self) -> Option<Timestamp> {
+        Timestamp::from_nanos(self.0.timestamp_min)
     }
-    pub fn set_timestamp_min(&mut self, timestamp_min: SystemTime) {
-        let t = timestamp_min
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .ok()
-            .and_then(|t| t.as_nanos().try_into().ok())
-            .expect("failed to get nanoseconds since unix epoch from the argument");
-        assert_ne!(t, u64::MAX, "timestamp_min must not be `2^64 - 1`");
-        self.0.timestamp_min = t;
+    pub fn set_timestamp_min(&mut self, timestamp_min: Option<Timestamp>) {
+        self.0.timestamp_min = Timestamp::into_raw(timestamp_min);
     }
-    pub fn with_timestamp_min(mut self, timestamp_min: SystemTime) -> Self {
-        self.set_timestamp_min(timestamp_min);
+    pub const fn with_timestamp_min(mut self, timestamp_min: Option<Timestamp>) -> Self {
+        self.0.timestamp_min = Timestamp::into_raw(timestamp_min);
         self
     }
 
-    pub fn timestamp_max(&self) -> SystemTime {
-        SystemTime::UNIX_EPOCH + Duration::from_nanos(self.0.timestamp_max)
+    pub const fn timestamp_max(&self) -> Option<Timestamp> {
+        Timestamp::from_nanos(self.0.timestamp_max)
     }
-    pub fn set_timestamp_max(&mut self, timestamp_max: SystemTime) {
-        let t = timestamp_max
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .ok()
-            .and_then(|t| t.as_nanos().try_into().ok())
-            .expect("failed to get nanoseconds since unix epoch from the argument");
-        assert_ne!(t, u64::MAX, "timestamp_max must not be `2^64 - 1`");
-        self.0.timestamp_max = t;
+    pub fn set_timestamp_max(&mut self, timestamp_max: Option<Timestamp>) {
+        self.0.timestamp_max = Timestamp::into_raw(timestamp_max);
     }
-    pub fn with_timestamp_max(mut self, timestamp_max: SystemTime) -> Self {
-        self.set_timestamp_max(timestamp_max);
+    pub const fn with_timestamp_max(mut self, timestamp_max: Option<Timestamp>) -> Self {
+        self.0.timestamp_max = Timestamp::into_raw(timestamp_max);
         self
     }
 
     pub const fn limit(&self) -> u32 {
         self.0.limit
     }
+    #[track_caller]
     pub fn set_limit(&mut self, limit: u32) {
-        assert_ne!(limit, 0, "limit must not be zero");
+        if let Err(e) = self.try_set_limit(limit) {
+            panic!("{e}");
+        }
+    }
+    pub fn try_set_limit(&mut self, limit: u32) -> Result<(), ValidationError> {
+        if limit == 0 {
+            return Err(ValidationError::Zero { field: "limit" });
+        }
         self.0.limit = limit;
+        Ok(())
     }
+    #[track_caller]
     pub fn with_limit(mut self, limit: u32) -> Self {
         self.set_limit(limit);
         self
     }
+    pub fn try_with_limit(mut self, limit: u32) -> Result<Self, ValidationError> {
+        self.try_set_limit(limit)?;
+        Ok(self)
+    }
 
     pub const fn flags(&self) -> Flags {
         Flags::from_bits_retain(self.0.flags)
@@ -112,6 +137,8 @@ impl Filter {
     }
 }
 
+impl_eq_by_bytes!(Filter);
+
 impl From<Raw> for Filter {
     fn from(value: Raw) -> Self {
         Filter(value)
@@ -122,3 +149,31 @@ impl From<Filter> for Raw {
         value.0
     }
 }
+
+#[cfg(test)]
+mod filter_spec {
+    use crate::error::ValidationError;
+
+    use super::Filter;
+
+    #[test]
+    fn try_new_validates_fields() {
+        assert_eq!(
+            Filter::try_new(u128::MAX, 1).err(),
+            Some(ValidationError::Max {
+                field: "account id",
+            }),
+        );
+        assert_eq!(
+            Filter::try_new(1, 0).err(),
+            Some(ValidationError::Zero { field: "limit" }),
+        );
+        assert!(Filter::try_new(0, 1).is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid account id: must not be the highest value of its type")]
+    fn with_account_id_panics_on_max() {
+        _ = Filter::new(1, 1).with_account_id(u128::MAX);
+    }
+}
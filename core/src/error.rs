@@ -8,6 +8,7 @@ pub use sys::{
     generated_safe::{
         self as sys_safe, CreateAccountErrorKind, CreateTransferErrorKind,
         InitStatusErrorKind as NewClientErrorKind, PacketStatusErrorKind as SendErrorKind,
+        ParseFlagsError,
     },
     tb_create_accounts_result_t as RawCreateAccountsIndividualApiResult,
     tb_create_transfers_result_t as RawCreateTransfersIndividualApiResult,
@@ -100,6 +101,14 @@ impl SendError {
         }
     }
 
+    /// Returns the [`IncompatibleReleaseError`] this [`SendError`] is caused by, if it's of the
+    /// [`SendErrorKind::ClientReleaseTooLow`] or [`SendErrorKind::ClientReleaseTooHigh`] kind.
+    ///
+    /// The same [`IncompatibleReleaseError`] is returned as the [`Error::source()`].
+    pub fn incompatible_release(self) -> Option<IncompatibleReleaseError> {
+        IncompatibleReleaseError::try_from(self).ok()
+    }
+
     pub fn code(self) -> NonZeroU8 {
         self.0
     }
@@ -137,7 +146,24 @@ impl fmt::Display for SendError {
     }
 }
 
-impl Error for SendError {}
+impl Error for SendError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        static CLIENT_TOO_OLD: IncompatibleReleaseError = IncompatibleReleaseError {
+            client_too_old: true,
+        };
+        static CLIENT_TOO_NEW: IncompatibleReleaseError = IncompatibleReleaseError {
+            client_too_old: false,
+        };
+
+        self.incompatible_release().map(|e| {
+            if e.client_too_old {
+                &CLIENT_TOO_OLD as _
+            } else {
+                &CLIENT_TOO_NEW as _
+            }
+        })
+    }
+}
 
 impl From<SendErrorKind> for SendError {
     /// Constructs a [`SendError`] out of the provided [`SendErrorKind`].
@@ -154,6 +180,163 @@ impl From<SendErrorKind> for SendError {
     }
 }
 
+/// Error of validating a field value of an [`Account`], [`Transfer`], [`QueryFilter`] or
+/// [`account::Filter`].
+///
+/// [`Account`]: crate::Account
+/// [`Transfer`]: crate::Transfer
+/// [`QueryFilter`]: crate::QueryFilter
+/// [`account::Filter`]: crate::account::Filter
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ValidationError {
+    /// Field value must not be zero.
+    Zero { field: &'static str },
+    /// Field value must not be the highest value of its type, being reserved.
+    Max { field: &'static str },
+    /// Timestamp must be representable as nanoseconds since UNIX epoch in 64 bits.
+    TimestampOutOfRange { field: &'static str },
+    /// Duration must be a whole number of seconds representable in 32 bits.
+    DurationOutOfRange { field: &'static str },
+}
+
+impl ValidationError {
+    /// Returns the name of the invalid field.
+    pub fn field(self) -> &'static str {
+        match self {
+            Self::Zero { field }
+            | Self::Max { field }
+            | Self::TimestampOutOfRange { field }
+            | Self::DurationOutOfRange { field } => field,
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Zero { field } => write!(f, "Invalid {field}: must not be zero"),
+            Self::Max { field } => {
+                write!(
+                    f,
+                    "Invalid {field}: must not be the highest value of its type"
+                )
+            }
+            Self::TimestampOutOfRange { field } => write!(
+                f,
+                "Invalid {field}: must be representable as 64-bit nanoseconds since UNIX epoch",
+            ),
+            Self::DurationOutOfRange { field } => write!(
+                f,
+                "Invalid {field}: must be a whole number of seconds representable in 32 bits",
+            ),
+        }
+    }
+}
+
+impl Error for ValidationError {}
+
+/// Error of the client's release being incompatible with the cluster's one.
+///
+/// The cluster's release itself is not reported by the `tb_client` library, only whether the
+/// client's one is too old or too new for it.
+#[derive(Clone, Copy, Debug)]
+pub struct IncompatibleReleaseError {
+    client_too_old: bool,
+}
+
+impl IncompatibleReleaseError {
+    /// Returns the release of this client (see [`TIGERBEETLE_RELEASE`]).
+    ///
+    /// [`TIGERBEETLE_RELEASE`]: crate::TIGERBEETLE_RELEASE
+    pub fn client_release(self) -> &'static str {
+        sys::TIGERBEETLE_RELEASE
+    }
+
+    /// Indicates whether the client's release is older than the cluster supports.
+    pub fn is_client_too_old(self) -> bool {
+        self.client_too_old
+    }
+
+    /// Indicates whether the client's release is newer than the cluster's one.
+    pub fn is_client_too_new(self) -> bool {
+        !self.client_too_old
+    }
+}
+
+impl fmt::Display for IncompatibleReleaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Client release {} is too {} for the cluster",
+            self.client_release(),
+            if self.client_too_old { "old" } else { "new" },
+        )
+    }
+}
+
+impl Error for IncompatibleReleaseError {}
+
+impl TryFrom<SendError> for IncompatibleReleaseError {
+    type Error = SendError;
+
+    /// Converts the provided [`SendError`] into an [`IncompatibleReleaseError`], if it's of the
+    /// [`SendErrorKind::ClientReleaseTooLow`] or [`SendErrorKind::ClientReleaseTooHigh`] kind.
+    fn try_from(value: SendError) -> Result<Self, Self::Error> {
+        let kind = value.kind();
+        if matches!(kind, SendErrorKind::ClientReleaseTooLow) {
+            Ok(Self {
+                client_too_old: true,
+            })
+        } else if matches!(kind, SendErrorKind::ClientReleaseTooHigh) {
+            Ok(Self {
+                client_too_old: false,
+            })
+        } else {
+            Err(value)
+        }
+    }
+}
+
+/// Error of checking the client's release compatibility with the cluster.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum CheckReleaseError {
+    Send(SendError),
+    Incompatible(IncompatibleReleaseError),
+}
+
+impl Error for CheckReleaseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(match self {
+            Self::Send(e) => e as _,
+            Self::Incompatible(e) => e as _,
+        })
+    }
+}
+
+impl fmt::Display for CheckReleaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Failed to check release: ")?;
+        match self {
+            Self::Send(e) => write!(f, "{e}"),
+            Self::Incompatible(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<SendError> for CheckReleaseError {
+    fn from(value: SendError) -> Self {
+        IncompatibleReleaseError::try_from(value).map_or_else(Self::Send, Self::Incompatible)
+    }
+}
+
+impl From<IncompatibleReleaseError> for CheckReleaseError {
+    fn from(value: IncompatibleReleaseError) -> Self {
+        Self::Incompatible(value)
+    }
+}
+
 #[derive(Clone, Copy)]
 pub struct CreateAccountError(pub(crate) NonZeroU32);
 
@@ -191,7 +374,12 @@ impl fmt::Debug for CreateAccountError {
 
 impl fmt::Display for CreateAccountError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:?}", self.kind())
+        let kind = self.kind();
+        if matches!(kind, CreateAccountErrorKind::UnstableUncategorized) {
+            write!(f, "Unknown error status: {}", self.code())
+        } else {
+            write!(f, "{}", kind.description())
+        }
     }
 }
 
@@ -301,9 +489,9 @@ impl fmt::Display for CreateAccountsIndividualApiError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "`{}` error occurred at account with index {}",
-            self.inner(),
+            "Error occurred at account with index {}: {}",
             self.index(),
+            self.inner(),
         )
     }
 }
@@ -443,7 +631,12 @@ impl fmt::Debug for CreateTransferError {
 
 impl fmt::Display for CreateTransferError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:?}", self.kind())
+        let kind = self.kind();
+        if matches!(kind, CreateTransferErrorKind::UnstableUncategorized) {
+            write!(f, "Unknown error status: {}", self.code())
+        } else {
+            write!(f, "{}", kind.description())
+        }
     }
 }
 
@@ -553,9 +746,9 @@ impl fmt::Display for CreateTransfersIndividualApiError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "`{}` error occurred at account with index {}",
-            self.inner(),
+            "Error occurred at transfer with index {}: {}",
             self.index(),
+            self.inner(),
         )
     }
 }
@@ -654,3 +847,66 @@ impl From<CreateTransfersApiError> for CreateTransfersError {
         Self::Api(value)
     }
 }
+
+#[cfg(test)]
+mod error_spec {
+    use std::error::Error as _;
+
+    use std::num::NonZeroU32;
+
+    use super::{
+        CreateAccountError, CreateAccountErrorKind, CreateTransferError, CreateTransferErrorKind,
+        IncompatibleReleaseError, SendError, SendErrorKind, ValidationError,
+    };
+
+    #[test]
+    fn send_error_exposes_incompatible_release() {
+        let too_old = SendError::from(SendErrorKind::ClientReleaseTooLow);
+        let too_new = SendError::from(SendErrorKind::ClientReleaseTooHigh);
+        let evicted = SendError::from(SendErrorKind::ClientEvicted);
+
+        assert!(too_old.incompatible_release().unwrap().is_client_too_old());
+        assert!(too_new.incompatible_release().unwrap().is_client_too_new());
+        assert!(evicted.incompatible_release().is_none());
+
+        let source = too_old.source().unwrap();
+        assert!(source
+            .downcast_ref::<IncompatibleReleaseError>()
+            .unwrap()
+            .is_client_too_old());
+        assert!(evicted.source().is_none());
+    }
+
+    #[test]
+    fn validation_error_display() {
+        let e = ValidationError::Zero {
+            field: "account id",
+        };
+
+        assert_eq!(e.field(), "account id");
+        assert_eq!(e.to_string(), "Invalid account id: must not be zero");
+        assert_eq!(
+            ValidationError::Max { field: "limit" }.to_string(),
+            "Invalid limit: must not be the highest value of its type",
+        );
+    }
+
+    #[test]
+    fn create_error_display_describes_kind() {
+        assert_eq!(
+            CreateTransferError::from(CreateTransferErrorKind::ExceedsCredits).to_string(),
+            CreateTransferErrorKind::ExceedsCredits.description(),
+        );
+        assert!(CreateTransferErrorKind::ExceedsCredits
+            .description()
+            .contains("debits_must_not_exceed_credits"));
+        assert_eq!(
+            CreateAccountError::from(CreateAccountErrorKind::LedgerMustNotBeZero).to_string(),
+            "The account was not created, because its ledger is zero",
+        );
+        assert_eq!(
+            CreateAccountError(NonZeroU32::new(u32::MAX).unwrap()).to_string(),
+            "Unknown error status: 4294967295",
+        );
+    }
+}
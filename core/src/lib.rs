@@ -7,11 +7,36 @@
     clippy::wildcard_enum_match_arm
 )]
 
+/// Implements [`PartialEq`], [`Eq`] and [`Hash`] for the provided [`Pod`] type by its bytes.
+///
+/// The raw types have neither padding nor floating point fields, so equal bytes mean equal values.
+///
+/// [`Hash`]: std::hash::Hash
+/// [`Pod`]: bytemuck::Pod
+macro_rules! impl_eq_by_bytes {
+    ($ty:ty) => {
+        impl PartialEq for $ty {
+            fn eq(&self, other: &Self) -> bool {
+                bytemuck::bytes_of(self) == bytemuck::bytes_of(other)
+            }
+        }
+
+        impl Eq for $ty {}
+
+        impl std::hash::Hash for $ty {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                bytemuck::bytes_of(self).hash(state);
+            }
+        }
+    };
+}
+
 pub mod account;
 mod callback;
 pub mod error;
 mod packet;
 pub mod query_filter;
+mod timestamp;
 pub mod transfer;
 pub mod util;
 
@@ -23,6 +48,8 @@ pub use account::Account;
 pub use callback::*;
 pub use packet::*;
 pub use query_filter::QueryFilter;
+pub use sys::{TIGERBEETLE_COMMIT, TIGERBEETLE_RELEASE};
+pub use timestamp::Timestamp;
 pub use transfer::Transfer;
 
 type CompletionCallbackRawFn =
@@ -134,6 +134,13 @@ impl Operation {
     const CODE_RANGE: std::ops::RangeInclusive<u8> =
         sys::generated_safe::MIN_OPERATION_CODE..=sys::generated_safe::MAX_OPERATION_CODE;
 
+    /// Creates an [`Operation`] out of its raw `code`.
+    ///
+    /// Allows to submit operations not covered by [`OperationKind`] yet.
+    pub const fn from_code(code: u8) -> Self {
+        Operation(code)
+    }
+
     pub fn kind(self) -> OperationKind {
         if Self::CODE_RANGE.contains(&self.0)
             && !sys_safe::EXCLUDED_OPERATION_CODES.contains(&self.0)
@@ -1,10 +1,9 @@
-use std::{
-    fmt,
-    time::{Duration, SystemTime},
-};
+use std::fmt;
 
 use bytemuck::{Pod, TransparentWrapper, Zeroable};
 
+use crate::{error::ValidationError, Timestamp};
+
 pub use sys::generated_safe::QueryFilterFlags as Flags;
 pub use sys::tb_query_filter_t as Raw;
 
@@ -34,6 +33,11 @@ impl QueryFilter {
         Self(Raw::zeroed()).with_limit(limit)
     }
 
+    /// Same as [`QueryFilter::new()`], but fails with a [`ValidationError`] instead of panicking.
+    pub fn try_new(limit: u32) -> Result<Self, ValidationError> {
+        Self(Raw::zeroed()).try_with_limit(limit)
+    }
+
     pub const fn from_raw(raw: Raw) -> Self {
         Self(raw)
     }
@@ -102,51 +106,53 @@ impl QueryFilter {
         self
     }
 
-    pub fn timestamp_min(&self) -> SystemTime {
-        SystemTime::UNIX_EPOCH + Duration::from_nanos(self.0.timestamp_min)
+    pub const fn timestamp_min(&self) -> Option<Timestamp> {
+        Timestamp::from_nanos(self.0.timestamp_min)
     }
-    pub fn set_timestamp_min(&mut self, timestamp_min: SystemTime) {
-        let t = timestamp_min
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .ok()
-            .and_then(|t| t.as_nanos().try_into().ok())
-            .expect("failed to get nanoseconds since unix epoch from the argument");
-        assert_ne!(t, u64::MAX, "timestamp_min must not be `2^64 - 1`");
-        self.0.timestamp_min = t;
+    pub fn set_timestamp_min(&mut self, timestamp_min: Option<Timestamp>) {
+        self.0.timestamp_min = Timestamp::into_raw(timestamp_min);
     }
-    pub fn with_timestamp_min(mut self, timestamp_min: SystemTime) -> Self {
-        self.set_timestamp_min(timestamp_min);
+    pub const fn with_timestamp_min(mut self, timestamp_min: Option<Timestamp>) -> Self {
+        self.0.timestamp_min = Timestamp::into_raw(timestamp_min);
         self
     }
 
-    pub fn timestamp_max(&self) -> SystemTime {
-        SystemTime::UNIX_EPOCH + Duration::from_nanos(self.0.timestamp_max)
+    pub const fn timestamp_max(&self) -> Option<Timestamp> {
+        Timestamp::from_nanos(self.0.timestamp_max)
     }
-    pub fn set_timestamp_max(&mut self, timestamp_max: SystemTime) {
-        let t = timestamp_max
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .ok()
-            .and_then(|t| t.as_nanos().try_into().ok())
-            .expect("failed to get nanoseconds since unix epoch from the argument");
-        assert_ne!(t, u64::MAX, "timestamp_max must not be `2^64 - 1`");
-        self.0.timestamp_max = t;
+    pub fn set_timestamp_max(&mut self, timestamp_max: Option<Timestamp>) {
+        self.0.timestamp_max = Timestamp::into_raw(timestamp_max);
     }
-    pub fn with_timestamp_max(mut self, timestamp_max: SystemTime) -> Self {
-        self.set_timestamp_max(timestamp_max);
+    pub const fn with_timestamp_max(mut self, timestamp_max: Option<Timestamp>) -> Self {
+        self.0.timestamp_max = Timestamp::into_raw(timestamp_max);
         self
     }
 
     pub const fn limit(&self) -> u32 {
         self.0.limit
     }
+    #[track_caller]
     pub fn set_limit(&mut self, limit: u32) {
-        assert_ne!(limit, 0, "limit must not be zero");
+        if let Err(e) = self.try_set_limit(limit) {
+            panic!("{e}");
+        }
+    }
+    pub fn try_set_limit(&mut self, limit: u32) -> Result<(), ValidationError> {
+        if limit == 0 {
+            return Err(ValidationError::Zero { field: "limit" });
+        }
         self.0.limit = limit;
+        Ok(())
     }
+    #[track_caller]
     pub fn with_limit(mut self, limit: u32) -> Self {
         self.set_limit(limit);
         self
     }
+    pub fn try_with_limit(mut self, limit: u32) -> Result<Self, ValidationError> {
+        self.try_set_limit(limit)?;
+        Ok(self)
+    }
 
     pub const fn flags(&self) -> Flags {
         Flags::from_bits_retain(self.0.flags)
@@ -160,6 +166,8 @@ impl QueryFilter {
     }
 }
 
+impl_eq_by_bytes!(QueryFilter);
+
 impl From<Raw> for QueryFilter {
     fn from(value: Raw) -> Self {
         Self(value)
@@ -170,3 +178,19 @@ impl From<QueryFilter> for Raw {
         value.0
     }
 }
+
+#[cfg(test)]
+mod query_filter_spec {
+    use crate::error::ValidationError;
+
+    use super::QueryFilter;
+
+    #[test]
+    fn try_new_validates_limit() {
+        assert_eq!(
+            QueryFilter::try_new(0).err(),
+            Some(ValidationError::Zero { field: "limit" }),
+        );
+        assert_eq!(QueryFilter::try_new(10).map(|f| f.limit()), Ok(10));
+    }
+}
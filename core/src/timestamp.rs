@@ -0,0 +1,230 @@
+use std::{
+    num::NonZeroU64,
+    time::{Duration, SystemTime},
+};
+
+use crate::error::ValidationError;
+
+/// [TigerBeetle] timestamp, being nanoseconds since UNIX epoch.
+///
+/// Zero timestamp means "unset" in [TigerBeetle], so is represented as [`None`] of an
+/// `Option<Timestamp>`, while the highest 64-bit value is reserved. Neither of them is a valid
+/// [`Timestamp`].
+///
+/// [TigerBeetle]: https://tigerbeetle.com
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Timestamp(NonZeroU64);
+
+impl Timestamp {
+    /// Smallest valid [`Timestamp`].
+    pub const MIN: Self = Self(NonZeroU64::MIN);
+
+    /// Largest valid [`Timestamp`].
+    pub const MAX: Self = match NonZeroU64::new(u64::MAX - 1) {
+        Some(nanos) => Self(nanos),
+        None => unreachable!(),
+    };
+
+    /// Creates a new [`Timestamp`] out of the provided nanoseconds since UNIX epoch.
+    ///
+    /// Returns [`None`] for zero and the reserved `u64::MAX` value.
+    pub const fn from_nanos(nanos: u64) -> Option<Self> {
+        if nanos == u64::MAX {
+            return None;
+        }
+        match NonZeroU64::new(nanos) {
+            Some(nanos) => Some(Self(nanos)),
+            None => None,
+        }
+    }
+
+    /// Same as [`Timestamp::from_nanos()`], but fails with a [`ValidationError`] describing why
+    /// the `nanos` are invalid.
+    fn try_from_nanos(nanos: u64) -> Result<Self, ValidationError> {
+        Self::from_nanos(nanos).ok_or(if nanos == 0 {
+            ValidationError::Zero { field: "timestamp" }
+        } else {
+            ValidationError::Max { field: "timestamp" }
+        })
+    }
+
+    /// Returns the nanoseconds since UNIX epoch of this [`Timestamp`].
+    pub const fn as_nanos(self) -> u64 {
+        self.0.get()
+    }
+
+    /// Converts the provided optional [`Timestamp`] into the raw nanoseconds, being zero if it's
+    /// unset.
+    pub(crate) const fn into_raw(timestamp: Option<Self>) -> u64 {
+        match timestamp {
+            Some(t) => t.as_nanos(),
+            None => 0,
+        }
+    }
+
+    /// Converts this [`Timestamp`] into a [`SystemTime`].
+    pub fn to_system_time(self) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_nanos(self.as_nanos())
+    }
+}
+
+impl From<Timestamp> for SystemTime {
+    fn from(value: Timestamp) -> Self {
+        value.to_system_time()
+    }
+}
+
+impl TryFrom<SystemTime> for Timestamp {
+    type Error = ValidationError;
+
+    /// Converts the provided [`SystemTime`] into a [`Timestamp`], failing if it's not after UNIX
+    /// epoch or doesn't fit into 64-bit nanoseconds.
+    fn try_from(value: SystemTime) -> Result<Self, Self::Error> {
+        let nanos = value
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .ok()
+            .and_then(|d| u64::try_from(d.as_nanos()).ok())
+            .ok_or(ValidationError::TimestampOutOfRange { field: "timestamp" })?;
+        Self::try_from_nanos(nanos)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<Timestamp> for chrono::DateTime<chrono::Utc> {
+    fn from(value: Timestamp) -> Self {
+        let nanos = value.as_nanos();
+        // PANIC: Unwrapping is OK here, because any 64-bit nanoseconds since UNIX epoch are
+        //        within the `chrono::DateTime` range.
+        chrono::DateTime::from_timestamp(
+            (nanos / 1_000_000_000) as i64,
+            (nanos % 1_000_000_000) as u32,
+        )
+        .unwrap()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<chrono::DateTime<chrono::Utc>> for Timestamp {
+    type Error = ValidationError;
+
+    fn try_from(value: chrono::DateTime<chrono::Utc>) -> Result<Self, Self::Error> {
+        let nanos = u64::try_from(value.timestamp())
+            .ok()
+            .and_then(|secs| secs.checked_mul(1_000_000_000))
+            .and_then(|nanos| nanos.checked_add(value.timestamp_subsec_nanos().into()))
+            .ok_or(ValidationError::TimestampOutOfRange { field: "timestamp" })?;
+        Self::try_from_nanos(nanos)
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<Timestamp> for time::OffsetDateTime {
+    fn from(value: Timestamp) -> Self {
+        // PANIC: Unwrapping is OK here, because any 64-bit nanoseconds since UNIX epoch are
+        //        within the `time::OffsetDateTime` range.
+        time::OffsetDateTime::from_unix_timestamp_nanos(value.as_nanos().into()).unwrap()
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<time::OffsetDateTime> for Timestamp {
+    type Error = ValidationError;
+
+    fn try_from(value: time::OffsetDateTime) -> Result<Self, Self::Error> {
+        let nanos = u64::try_from(value.unix_timestamp_nanos())
+            .map_err(|_| ValidationError::TimestampOutOfRange { field: "timestamp" })?;
+        Self::try_from_nanos(nanos)
+    }
+}
+
+#[cfg(test)]
+mod timestamp_spec {
+    use std::time::{Duration, SystemTime};
+
+    use crate::error::ValidationError;
+
+    use super::Timestamp;
+
+    #[test]
+    fn rejects_reserved_nanos() {
+        assert_eq!(Timestamp::from_nanos(0), None);
+        assert_eq!(Timestamp::from_nanos(u64::MAX), None);
+        assert_eq!(Timestamp::from_nanos(1), Some(Timestamp::MIN));
+        assert_eq!(Timestamp::from_nanos(u64::MAX - 1), Some(Timestamp::MAX));
+        assert_eq!(Timestamp::into_raw(None), 0);
+        assert_eq!(Timestamp::into_raw(Some(Timestamp::MAX)), u64::MAX - 1);
+    }
+
+    #[test]
+    fn converts_system_time() {
+        // Some platforms have 100ns `SystemTime` precision only.
+        let time = SystemTime::UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_700);
+
+        let timestamp = Timestamp::try_from(time).unwrap();
+
+        assert_eq!(timestamp.as_nanos(), 1_700_000_000_123_456_700);
+        assert_eq!(SystemTime::from(timestamp), time);
+    }
+
+    #[test]
+    fn rejects_out_of_range_system_time() {
+        assert_eq!(
+            Timestamp::try_from(SystemTime::UNIX_EPOCH),
+            Err(ValidationError::Zero { field: "timestamp" }),
+        );
+        assert_eq!(
+            Timestamp::try_from(SystemTime::UNIX_EPOCH - Duration::from_secs(1)),
+            Err(ValidationError::TimestampOutOfRange { field: "timestamp" }),
+        );
+        assert_eq!(
+            Timestamp::try_from(
+                SystemTime::UNIX_EPOCH + Duration::from_secs(u64::MAX / 1_000_000_000 + 1),
+            ),
+            Err(ValidationError::TimestampOutOfRange { field: "timestamp" }),
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn converts_chrono() {
+        use chrono::{DateTime, Utc};
+
+        let timestamp = Timestamp::from_nanos(1_700_000_000_123_456_789).unwrap();
+
+        let time = DateTime::<Utc>::from(timestamp);
+
+        assert_eq!(time.timestamp(), 1_700_000_000);
+        assert_eq!(time.timestamp_subsec_nanos(), 123_456_789);
+        assert_eq!(Timestamp::try_from(time), Ok(timestamp));
+        assert_eq!(
+            Timestamp::try_from(DateTime::<Utc>::from_timestamp(-1, 0).unwrap()),
+            Err(ValidationError::TimestampOutOfRange { field: "timestamp" }),
+        );
+        assert_eq!(
+            Timestamp::try_from(DateTime::<Utc>::from(Timestamp::MAX)),
+            Ok(Timestamp::MAX),
+        );
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn converts_time() {
+        use time::OffsetDateTime;
+
+        let timestamp = Timestamp::from_nanos(1_700_000_000_123_456_789).unwrap();
+
+        let time = OffsetDateTime::from(timestamp);
+
+        assert_eq!(time.unix_timestamp_nanos(), 1_700_000_000_123_456_789);
+        assert_eq!(Timestamp::try_from(time), Ok(timestamp));
+        assert_eq!(
+            Timestamp::try_from(OffsetDateTime::UNIX_EPOCH - time::Duration::SECOND),
+            Err(ValidationError::TimestampOutOfRange { field: "timestamp" }),
+        );
+        assert_eq!(
+            Timestamp::try_from(OffsetDateTime::from(Timestamp::MAX)),
+            Ok(Timestamp::MAX),
+        );
+    }
+}
@@ -1,7 +1,9 @@
-use std::time::{Duration, SystemTime};
+use std::time::Duration;
 
 use bytemuck::{Pod, TransparentWrapper, Zeroable};
 
+use crate::{error::ValidationError, Timestamp};
+
 pub use sys::generated_safe::TransferFlags as Flags;
 pub use sys::tb_transfer_t as Raw;
 
@@ -15,6 +17,11 @@ impl Transfer {
         Transfer(Raw::zeroed()).with_id(id)
     }
 
+    /// Same as [`Transfer::new()`], but fails with a [`ValidationError`] instead of panicking.
+    pub fn try_new(id: u128) -> Result<Self, ValidationError> {
+        Transfer(Raw::zeroed()).try_with_id(id)
+    }
+
     pub const fn from_raw(raw: Raw) -> Self {
         Transfer(raw)
     }
@@ -33,19 +40,33 @@ impl Transfer {
     }
     #[track_caller]
     pub fn set_id(&mut self, id: u128) {
-        assert_ne!(id, 0, "transfer id must not be zero");
-        assert_ne!(
-            id,
-            u128::MAX,
-            "transfer id must not be `2^128 - 1` (the highest 128-bit unsigned integer)"
-        );
+        if let Err(e) = self.try_set_id(id) {
+            panic!("{e}");
+        }
+    }
+    pub fn try_set_id(&mut self, id: u128) -> Result<(), ValidationError> {
+        if id == 0 {
+            return Err(ValidationError::Zero {
+                field: "transfer id",
+            });
+        }
+        if id == u128::MAX {
+            return Err(ValidationError::Max {
+                field: "transfer id",
+            });
+        }
         self.0.id = id;
+        Ok(())
     }
     #[track_caller]
     pub fn with_id(mut self, id: u128) -> Self {
         self.set_id(id);
         self
     }
+    pub fn try_with_id(mut self, id: u128) -> Result<Self, ValidationError> {
+        self.try_set_id(id)?;
+        Ok(self)
+    }
 
     pub const fn debit_account_id(&self) -> u128 {
         self.0.debit_account_id
@@ -159,6 +180,50 @@ impl Transfer {
         self
     }
 
+    /// Returns the timeout of this pending [`Transfer`] as a [`Duration`].
+    ///
+    /// Only applies to [`Transfer`]s with the [`Flags::PENDING`] flag.
+    pub const fn timeout_duration(&self) -> Duration {
+        Duration::from_secs(self.0.timeout as u64)
+    }
+    #[track_caller]
+    pub fn set_timeout_duration(&mut self, timeout: Duration) {
+        if let Err(e) = self.try_set_timeout_duration(timeout) {
+            panic!("{e}");
+        }
+    }
+    pub fn try_set_timeout_duration(&mut self, timeout: Duration) -> Result<(), ValidationError> {
+        if timeout.subsec_nanos() != 0 {
+            return Err(ValidationError::DurationOutOfRange { field: "timeout" });
+        }
+        self.0.timeout = timeout
+            .as_secs()
+            .try_into()
+            .map_err(|_| ValidationError::DurationOutOfRange { field: "timeout" })?;
+        Ok(())
+    }
+    #[track_caller]
+    pub fn with_timeout_duration(mut self, timeout: Duration) -> Self {
+        self.set_timeout_duration(timeout);
+        self
+    }
+    pub fn try_with_timeout_duration(mut self, timeout: Duration) -> Result<Self, ValidationError> {
+        self.try_set_timeout_duration(timeout)?;
+        Ok(self)
+    }
+
+    /// Returns the [`Timestamp`] this pending [`Transfer`] expires at, computed from its creation
+    /// [`Transfer::timestamp()`] and [`Transfer::timeout()`].
+    ///
+    /// Returns [`None`] if this [`Transfer`] is not pending, has no timeout or is not created yet.
+    pub fn expires_at(&self) -> Option<Timestamp> {
+        if !self.flags().contains(Flags::PENDING) || self.0.timeout == 0 {
+            return None;
+        }
+        let timeout = u64::from(self.0.timeout).checked_mul(1_000_000_000)?;
+        Timestamp::from_nanos(self.timestamp()?.as_nanos().checked_add(timeout)?)
+    }
+
     pub const fn amount(&self) -> u128 {
         self.0.amount
     }
@@ -170,8 +235,34 @@ impl Transfer {
         self
     }
 
-    pub fn timestamp(&self) -> SystemTime {
-        SystemTime::UNIX_EPOCH + Duration::from_nanos(self.0.timestamp)
+    pub const fn timestamp(&self) -> Option<Timestamp> {
+        Timestamp::from_nanos(self.0.timestamp)
+    }
+    pub fn set_timestamp(&mut self, timestamp: Option<Timestamp>) {
+        self.0.timestamp = Timestamp::into_raw(timestamp);
+    }
+    pub const fn with_timestamp(mut self, timestamp: Option<Timestamp>) -> Self {
+        self.0.timestamp = Timestamp::into_raw(timestamp);
+        self
+    }
+
+    /// Compares this [`Transfer`] with the `other` one by their content only, ignoring the fields
+    /// populated by the cluster (its timestamp).
+    ///
+    /// All the other fields are compared as is, which is stricter than how the cluster decides
+    /// whether an already existing [`Transfer`] is the same one (`Exists`) or a different one
+    /// (`ExistsWithDifferent*`):
+    /// - for a post or void of a pending [`Transfer`], the cluster fills the fields left zeroed
+    ///   with the ones of the pending [`Transfer`];
+    /// - for a balancing [`Transfer`], the cluster clamps the amount to the balance available.
+    ///
+    /// So, such a submitted [`Transfer`] may differ from its looked up version, while the cluster
+    /// still considers it the same one.
+    pub fn content_eq(&self, other: &Self) -> bool {
+        let (mut this, mut other) = (self.0, other.0);
+        this.timestamp = 0;
+        other.timestamp = 0;
+        Self(this) == Self(other)
     }
 }
 
@@ -195,6 +286,8 @@ impl std::fmt::Debug for Transfer {
     }
 }
 
+impl_eq_by_bytes!(Transfer);
+
 impl From<Raw> for Transfer {
     fn from(value: Raw) -> Self {
         Transfer(value)
@@ -205,3 +298,104 @@ impl From<Transfer> for Raw {
         value.0
     }
 }
+
+#[cfg(test)]
+mod transfer_spec {
+    use std::time::Duration;
+
+    use crate::{error::ValidationError, Timestamp};
+
+    use super::{Flags, Transfer};
+
+    #[test]
+    fn try_new_validates_id() {
+        assert_eq!(
+            Transfer::try_new(0).err(),
+            Some(ValidationError::Zero {
+                field: "transfer id",
+            }),
+        );
+        assert_eq!(
+            Transfer::try_new(u128::MAX).err(),
+            Some(ValidationError::Max {
+                field: "transfer id",
+            }),
+        );
+        assert_eq!(Transfer::try_new(1).map(|t| t.id()), Ok(1));
+    }
+
+    #[test]
+    fn converts_timeout_duration() {
+        let mut transfer = Transfer::new(1);
+
+        assert_eq!(
+            transfer.try_set_timeout_duration(Duration::from_secs(60)),
+            Ok(()),
+        );
+        assert_eq!(transfer.timeout(), 60);
+        assert_eq!(transfer.timeout_duration(), Duration::from_secs(60));
+
+        assert_eq!(
+            transfer.try_set_timeout_duration(Duration::from_secs(u32::MAX.into())),
+            Ok(()),
+        );
+        assert_eq!(transfer.timeout(), u32::MAX);
+    }
+
+    #[test]
+    fn rejects_unrepresentable_timeout_duration() {
+        let mut transfer = Transfer::new(1).with_timeout(60);
+        let err = Some(ValidationError::DurationOutOfRange { field: "timeout" });
+
+        assert_eq!(
+            transfer
+                .try_set_timeout_duration(Duration::from_millis(1500))
+                .err(),
+            err,
+        );
+        assert_eq!(
+            transfer
+                .try_set_timeout_duration(Duration::from_secs(u64::from(u32::MAX) + 1))
+                .err(),
+            err,
+        );
+        assert_eq!(transfer.timeout(), 60);
+    }
+
+    #[test]
+    fn computes_expires_at() {
+        let created = Timestamp::from_nanos(1_000_000_000);
+        let transfer = Transfer::new(1)
+            .with_flags(Flags::PENDING)
+            .with_timeout(60)
+            .with_timestamp(created);
+
+        assert_eq!(transfer.expires_at(), Timestamp::from_nanos(61_000_000_000),);
+    }
+
+    #[test]
+    fn expires_at_only_created_pending_with_timeout() {
+        let created = Timestamp::from_nanos(1_000_000_000);
+        let pending = Transfer::new(1)
+            .with_flags(Flags::PENDING)
+            .with_timeout(60)
+            .with_timestamp(created);
+
+        assert_eq!(pending.with_flags(Flags::empty()).expires_at(), None);
+        assert_eq!(pending.with_timeout(0).expires_at(), None);
+        assert_eq!(pending.with_timestamp(None).expires_at(), None);
+    }
+
+    #[test]
+    fn content_eq_ignores_timestamp() {
+        let submitted = Transfer::new(1)
+            .with_debit_account_id(2)
+            .with_credit_account_id(3)
+            .with_amount(4);
+        let created = submitted.with_timestamp(Timestamp::from_nanos(5));
+
+        assert_ne!(submitted, created);
+        assert!(submitted.content_eq(&created));
+        assert!(!submitted.content_eq(&created.with_amount(6)));
+    }
+}
@@ -2,8 +2,10 @@
 
 mod owned_slice;
 mod raw_const_ptr;
+mod scope;
 pub mod send_marker;
 
 pub use owned_slice::*;
 pub use raw_const_ptr::RawConstPtr;
+pub use scope::{scope, Scope};
 pub use send_marker::SendMarker;
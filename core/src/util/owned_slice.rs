@@ -1,4 +1,4 @@
-use std::{marker::PhantomData, mem, ptr::NonNull};
+use std::{marker::PhantomData, mem, ptr::NonNull, sync::Arc};
 
 use super::{send_marker, RawConstPtr, SendMarker};
 
@@ -228,6 +228,100 @@ impl<T: Sized> OwnedSlice<T> {
     }
 }
 
+impl<T> SendOwnedSlice<T> {
+    /// Creates owned slice borrowed from the provided `owner`, keeping the `owner` alive until the
+    /// slice is dropped.
+    ///
+    /// The `owner` is moved into a [`Box`], so its inline data (like the elements of an
+    /// `ArrayVec`) is copied once, while the heap data it points to (like of `Bytes`) is not.
+    fn from_owner<O>(owner: O, as_slice: impl FnOnce(&O) -> &[T]) -> Self
+    where
+        O: Send + 'static,
+    {
+        unsafe fn drop_impl<O>(_: NonNull<Erased>, _: usize, owner: usize) {
+            drop(Box::from_raw(sptr::from_exposed_addr_mut::<O>(owner)));
+        }
+        let owner = Box::into_raw(Box::new(owner));
+        // SAFETY: `owner` is a valid pointer, as has been just created out of a `Box`.
+        let slice = NonNull::from(as_slice(unsafe { &*owner }));
+        let owner = sptr::Strict::expose_addr(owner);
+        // SAFETY: `owner` is boxed, so the `slice` stays valid until the `drop_impl` is called.
+        unsafe { OwnedSlice::from_raw_parts(slice.cast(), slice.len(), owner, drop_impl::<O>) }
+    }
+
+    /// Creates a [`SendOwnedSlice`] sharing the slice of the provided `shared` one without
+    /// copying, so the slice stays alive until all the shares are dropped.
+    pub fn from_shared(shared: Arc<Self>) -> Self
+    where
+        T: Sync + 'static,
+    {
+        Self::from_owner(shared, |s| s.as_slice())
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<T, const CAP: usize> From<arrayvec::ArrayVec<T, CAP>> for SendOwnedSlice<T>
+where
+    T: Send + 'static,
+{
+    fn from(value: arrayvec::ArrayVec<T, CAP>) -> Self {
+        Self::from_owner(value, |v| v.as_slice())
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl<T> TryFrom<bytes::Bytes> for SendOwnedSlice<T>
+where
+    T: bytemuck::Pod,
+{
+    type Error = bytemuck::PodCastError;
+
+    /// Reinterprets the provided [`Bytes`] as a slice of `T` without copying.
+    ///
+    /// # Errors
+    ///
+    /// If the [`Bytes`] are not aligned for `T`, or their length is not a multiple of `T` size.
+    ///
+    /// [`Bytes`]: bytes::Bytes
+    fn try_from(value: bytes::Bytes) -> Result<Self, Self::Error> {
+        _ = bytemuck::try_cast_slice::<u8, T>(&value)?;
+        Ok(Self::from_owner(value, |b| bytemuck::cast_slice(b)))
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl<T> TryFrom<bytes::BytesMut> for SendOwnedSlice<T>
+where
+    T: bytemuck::Pod,
+{
+    type Error = bytemuck::PodCastError;
+
+    /// Reinterprets the provided [`BytesMut`] as a slice of `T` without copying.
+    ///
+    /// # Errors
+    ///
+    /// If the [`BytesMut`] are not aligned for `T`, or their length is not a multiple of `T` size.
+    ///
+    /// [`BytesMut`]: bytes::BytesMut
+    fn try_from(value: bytes::BytesMut) -> Result<Self, Self::Error> {
+        value.freeze().try_into()
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<A> From<smallvec::SmallVec<A>> for SendOwnedSlice<A::Item>
+where
+    A: smallvec::Array,
+    A::Item: Send + 'static,
+{
+    /// Doesn't copy the elements if the [`SmallVec`] has spilled onto the heap already.
+    ///
+    /// [`SmallVec`]: smallvec::SmallVec
+    fn from(value: smallvec::SmallVec<A>) -> Self {
+        value.into_vec().into()
+    }
+}
+
 impl<T, S> Drop for OwnedSlice<T, S>
 where
     S: SendMarker,
@@ -273,3 +367,65 @@ where
         self.owner.is_empty()
     }
 }
+
+#[cfg(test)]
+mod owned_slice_spec {
+    #[cfg(feature = "arrayvec")]
+    #[test]
+    fn from_arrayvec() {
+        use super::SendOwnedSlice;
+
+        let v = arrayvec::ArrayVec::<u128, 4>::from([1, 2, 3, 4]);
+
+        let slice = SendOwnedSlice::from(v);
+
+        assert_eq!(slice.as_slice(), &[1, 2, 3, 4]);
+    }
+
+    #[cfg(feature = "bytes")]
+    #[test]
+    fn from_bytes_without_copying() {
+        use super::SendOwnedSlice;
+
+        let data = bytes::Bytes::from(vec![1_u8, 2, 3, 4, 5, 6, 7, 8]);
+        let ptr = data.as_ptr();
+
+        let slice = SendOwnedSlice::<[u8; 4]>::try_from(data).unwrap();
+
+        assert_eq!(slice.as_slice().as_ptr().cast(), ptr);
+        assert_eq!(slice.as_slice(), &[[1, 2, 3, 4], [5, 6, 7, 8]]);
+    }
+
+    #[cfg(feature = "bytes")]
+    #[test]
+    fn from_bytes_with_slop() {
+        use super::SendOwnedSlice;
+
+        let data = bytes::Bytes::from(vec![0_u8; 15]);
+
+        assert_eq!(
+            SendOwnedSlice::<u8>::try_from(data.clone()).map(|s| s.len()),
+            Ok(15),
+        );
+        assert_eq!(
+            SendOwnedSlice::<[u8; 2]>::try_from(data).err(),
+            Some(bytemuck::PodCastError::OutputSliceWouldHaveSlop),
+        );
+    }
+    #[test]
+    fn from_shared_without_copying() {
+        use std::sync::Arc;
+
+        use super::SendOwnedSlice;
+
+        let shared = Arc::new(SendOwnedSlice::from(vec![1_u128, 2, 3]));
+        let ptr = shared.as_slice().as_ptr();
+
+        let slice = SendOwnedSlice::from_shared(Arc::clone(&shared));
+
+        assert_eq!(slice.as_slice().as_ptr(), ptr);
+        assert_eq!(slice.as_slice(), &[1, 2, 3]);
+        drop(shared);
+        assert_eq!(slice.as_slice(), &[1, 2, 3]);
+    }
+}
@@ -0,0 +1,158 @@
+//! Scoped borrowing of slices, allowing to submit them without copying.
+
+use std::{
+    marker::PhantomData,
+    ptr::NonNull,
+    sync::{Arc, Condvar, Mutex},
+};
+
+use super::{Erased, OwnedSlice, SendOwnedSlice};
+
+/// Scope to borrow slices in, created by [`scope()`].
+///
+/// See [`Scope::borrow()`] for details.
+pub struct Scope<'env> {
+    state: Arc<ScopeState>,
+    /// Invariance over `'env`, to make sure `'env` cannot shrink.
+    marker: PhantomData<&'env mut &'env ()>,
+}
+
+#[derive(Default)]
+struct ScopeState {
+    /// Number of borrowed slices not dropped yet.
+    borrowed: Mutex<usize>,
+    released: Condvar,
+}
+
+/// Creates a [`Scope`] for borrowing slices as [`SendOwnedSlice`]s.
+///
+/// Doesn't return until every slice borrowed via [`Scope::borrow()`] is dropped (even if `f`
+/// panics), so the borrowed data is guaranteed to outlive any [`Packet`] submitted with it. For
+/// the slices passed to the high-level client, this happens once their requests complete.
+///
+/// Leaking a borrowed slice (or returning it out of `f`) makes this function block forever.
+///
+/// # Blocking
+///
+/// This function blocks the calling thread until the borrowed slices are released, and `f` is a
+/// synchronous closure, so futures of the async high-level client cannot be awaited inside it.
+/// Use it with a blocking client, or from a thread not driving any async executor (like the one
+/// of `tokio::task::spawn_blocking()`). Calling it on an executor thread stalls that executor,
+/// and deadlocks a current-thread runtime completely, because the requests holding the borrowed
+/// slices can never complete there.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use tigerbeetle_unofficial::{blocking, core::util::scope, Transfer};
+///
+/// fn submit(client: &blocking::Client, transfers: &[Transfer]) {
+///     scope(|s| client.create_transfers(s.borrow(transfers))).unwrap();
+/// }
+/// ```
+///
+/// [`Packet`]: crate::Packet
+pub fn scope<'env, F, R>(f: F) -> R
+where
+    F: FnOnce(&Scope<'env>) -> R,
+{
+    /// Waits for all the borrowed slices being released, even on unwinding.
+    struct WaitOnDrop<'s>(&'s ScopeState);
+
+    impl Drop for WaitOnDrop<'_> {
+        fn drop(&mut self) {
+            self.0.wait();
+        }
+    }
+
+    let scope = Scope {
+        state: Arc::default(),
+        marker: PhantomData,
+    };
+    let _guard = WaitOnDrop(&scope.state);
+    f(&scope)
+}
+
+impl<'env> Scope<'env> {
+    /// Borrows the provided `slice` as a [`SendOwnedSlice`] without copying it.
+    ///
+    /// The enclosing [`scope()`] won't return until the returned [`SendOwnedSlice`] is dropped.
+    pub fn borrow<T>(&self, slice: &'env [T]) -> SendOwnedSlice<T>
+    where
+        T: Sync,
+    {
+        unsafe fn drop_impl(_: NonNull<Erased>, _: usize, state: usize) {
+            Arc::from_raw(sptr::from_exposed_addr::<ScopeState>(state)).release();
+        }
+
+        *self.state.borrowed.lock().unwrap() += 1;
+        let state = sptr::Strict::expose_addr(Arc::into_raw(Arc::clone(&self.state)));
+        // SAFETY: `scope()` doesn't return until the `drop_impl` is called, so the `slice` outlives
+        //         the returned `SendOwnedSlice`. Sending it to another thread is OK, because
+        //         `T: Sync`.
+        unsafe {
+            OwnedSlice::from_raw_parts(NonNull::from(slice).cast(), slice.len(), state, drop_impl)
+        }
+    }
+}
+
+impl ScopeState {
+    fn release(&self) {
+        let mut borrowed = self.borrowed.lock().unwrap();
+        *borrowed -= 1;
+        if *borrowed == 0 {
+            self.released.notify_all();
+        }
+    }
+
+    fn wait(&self) {
+        let borrowed = self.borrowed.lock().unwrap();
+        drop(self.released.wait_while(borrowed, |b| *b > 0).unwrap());
+    }
+}
+
+#[cfg(test)]
+mod scope_spec {
+    use std::{
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        thread,
+        time::Duration,
+    };
+
+    use super::scope;
+
+    #[test]
+    fn borrows_without_copying() {
+        let data = [1_u128, 2, 3];
+
+        scope(|s| {
+            let borrowed = s.borrow(&data);
+
+            assert_eq!(borrowed.as_slice().as_ptr(), data.as_ptr());
+            assert_eq!(borrowed.as_slice(), &data);
+        });
+    }
+
+    #[test]
+    fn waits_for_borrowed_slices_to_drop() {
+        let data = vec![1_u128, 2, 3];
+        let dropped = Arc::new(AtomicBool::new(false));
+
+        scope(|s| {
+            let borrowed = s.borrow(&data);
+            let dropped = Arc::clone(&dropped);
+            // Emulate a completion callback running on another thread.
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(10));
+                assert_eq!(borrowed.as_slice(), &[1, 2, 3]);
+                dropped.store(true, Ordering::SeqCst);
+                drop(borrowed);
+            });
+        });
+
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+}
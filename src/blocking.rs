@@ -0,0 +1,330 @@
+//! Blocking (synchronous) version of the high-level [`Client`].
+//!
+//! Every request parks the calling thread on a [`Condvar`] until the completion callback fires, so
+//! no async executor is required.
+
+use std::{
+    error::Error,
+    fmt,
+    ops::ControlFlow,
+    sync::{Arc, Condvar, Mutex},
+    time::Duration,
+};
+
+use core::{
+    error::{CreateAccountsError, CreateTransfersError, NewClientError, SendError},
+    util::{RawConstPtr, SendOwnedSlice},
+};
+
+use crate::{account, operation, Account, Callbacks, Operation, QueryFilter, Transfer};
+
+/// Blocking [TigerBeetle] client.
+///
+/// Provides the same operations as the async [`crate::Client`], but blocks the current thread
+/// until the reply is received.
+///
+/// [TigerBeetle]: https://tigerbeetle.com
+pub struct Client {
+    inner: core::Client<&'static Callbacks>,
+}
+
+/// Handle to a blocking [`Client`] performing requests with a timeout.
+///
+/// Created by [`Client::timeout()`].
+#[derive(Clone, Copy)]
+pub struct Timeout<'c> {
+    client: &'c Client,
+    timeout: Duration,
+}
+
+/// Error returned by [`Timeout`] requests, indicating that the reply wasn't received in time.
+///
+/// The request itself is not cancelled, and may still be applied by the cluster.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Elapsed(());
+
+/// Synchronization point between the completion callback and the requesting thread.
+struct Slot<T> {
+    reply: Mutex<Option<T>>,
+    ready: Condvar,
+}
+
+impl Client {
+    pub fn new<A>(cluster_id: u128, address: A) -> Result<Self, NewClientError>
+    where
+        A: AsRef<[u8]>,
+    {
+        Ok(Client {
+            inner: core::Client::with_callback(cluster_id, address, &Callbacks)?,
+        })
+    }
+
+    /// Returns a handle performing requests of this [`Client`] with the provided `timeout`.
+    pub fn timeout(&self, timeout: Duration) -> Timeout<'_> {
+        Timeout {
+            client: self,
+            timeout,
+        }
+    }
+
+    /// Executes the provided [`Operation`] with the provided `input`.
+    pub fn execute<Op>(&self, input: impl Into<Op::Input>) -> Result<Op::Output, Op::Error>
+    where
+        Op: Operation + 'static,
+    {
+        match self.submit::<Op>(input.into()) {
+            ControlFlow::Continue(slot) => slot.wait(),
+            ControlFlow::Break(output) => Ok(output),
+        }
+    }
+
+    pub fn create_accounts<T>(&self, accounts: T) -> Result<(), CreateAccountsError>
+    where
+        T: Into<SendOwnedSlice<Account>>,
+    {
+        self.execute::<operation::CreateAccounts>(accounts)
+    }
+
+    pub fn create_transfers<T>(&self, transfers: T) -> Result<(), CreateTransfersError>
+    where
+        T: Into<SendOwnedSlice<Transfer>>,
+    {
+        self.execute::<operation::CreateTransfers>(transfers)
+    }
+
+    pub fn get_account_balances<T>(&self, filter: T) -> Result<Vec<account::Balance>, SendError>
+    where
+        T: RawConstPtr<Target = account::Filter> + Send + 'static,
+    {
+        self.execute::<operation::GetAccountBalances>(SendOwnedSlice::from_single(filter))
+    }
+
+    pub fn get_account_transfers<T>(&self, filter: T) -> Result<Vec<Transfer>, SendError>
+    where
+        T: RawConstPtr<Target = account::Filter> + Send + 'static,
+    {
+        self.execute::<operation::GetAccountTransfers>(SendOwnedSlice::from_single(filter))
+    }
+
+    pub fn lookup_accounts<T>(&self, ids: T) -> Result<Vec<Account>, SendError>
+    where
+        T: Into<SendOwnedSlice<u128>>,
+    {
+        self.execute::<operation::LookupAccounts>(ids)
+    }
+
+    pub fn lookup_transfers<T>(&self, ids: T) -> Result<Vec<Transfer>, SendError>
+    where
+        T: Into<SendOwnedSlice<u128>>,
+    {
+        self.execute::<operation::LookupTransfers>(ids)
+    }
+
+    pub fn query_accounts<T>(&self, filter: T) -> Result<Vec<Account>, SendError>
+    where
+        T: RawConstPtr<Target = QueryFilter> + Send + 'static,
+    {
+        self.execute::<operation::QueryAccounts>(SendOwnedSlice::from_single(filter))
+    }
+
+    pub fn query_transfers<T>(&self, filter: T) -> Result<Vec<Transfer>, SendError>
+    where
+        T: RawConstPtr<Target = QueryFilter> + Send + 'static,
+    {
+        self.execute::<operation::QueryTransfers>(SendOwnedSlice::from_single(filter))
+    }
+
+    /// Submits the provided [`Operation`], returning the [`Slot`] to wait its reply on.
+    ///
+    /// Returns [`ControlFlow::Break`] with the output, if there is nothing to submit.
+    #[allow(clippy::type_complexity)]
+    fn submit<Op>(
+        &self,
+        input: Op::Input,
+    ) -> ControlFlow<Op::Output, Arc<Slot<Result<Op::Output, Op::Error>>>>
+    where
+        Op: Operation + 'static,
+    {
+        let data = Op::encode(input)?;
+        #[cfg(feature = "tracing")]
+        let _entered = crate::trace::span(Op::CODE).entered();
+        let slot = Arc::new(Slot::default());
+        crate::submit(&self.inner, data, Op::CODE, {
+            let slot = Arc::clone(&slot);
+            // The requesting thread may not wait for the reply anymore due to the `Timeout`, so
+            // nobody will observe it, which is OK.
+            move |reply| slot.complete(reply.map_err(Into::into).and_then(Op::decode))
+        });
+        ControlFlow::Continue(slot)
+    }
+}
+
+impl Timeout<'_> {
+    /// Executes the provided [`Operation`] with the provided `input`.
+    pub fn execute<Op>(
+        &self,
+        input: impl Into<Op::Input>,
+    ) -> Result<Result<Op::Output, Op::Error>, Elapsed>
+    where
+        Op: Operation + 'static,
+    {
+        match self.client.submit::<Op>(input.into()) {
+            ControlFlow::Continue(slot) => slot.wait_timeout(self.timeout),
+            ControlFlow::Break(output) => Ok(Ok(output)),
+        }
+    }
+
+    pub fn create_accounts<T>(
+        &self,
+        accounts: T,
+    ) -> Result<Result<(), CreateAccountsError>, Elapsed>
+    where
+        T: Into<SendOwnedSlice<Account>>,
+    {
+        self.execute::<operation::CreateAccounts>(accounts)
+    }
+
+    pub fn create_transfers<T>(
+        &self,
+        transfers: T,
+    ) -> Result<Result<(), CreateTransfersError>, Elapsed>
+    where
+        T: Into<SendOwnedSlice<Transfer>>,
+    {
+        self.execute::<operation::CreateTransfers>(transfers)
+    }
+
+    pub fn get_account_balances<T>(
+        &self,
+        filter: T,
+    ) -> Result<Result<Vec<account::Balance>, SendError>, Elapsed>
+    where
+        T: RawConstPtr<Target = account::Filter> + Send + 'static,
+    {
+        self.execute::<operation::GetAccountBalances>(SendOwnedSlice::from_single(filter))
+    }
+
+    pub fn get_account_transfers<T>(
+        &self,
+        filter: T,
+    ) -> Result<Result<Vec<Transfer>, SendError>, Elapsed>
+    where
+        T: RawConstPtr<Target = account::Filter> + Send + 'static,
+    {
+        self.execute::<operation::GetAccountTransfers>(SendOwnedSlice::from_single(filter))
+    }
+
+    pub fn lookup_accounts<T>(&self, ids: T) -> Result<Result<Vec<Account>, SendError>, Elapsed>
+    where
+        T: Into<SendOwnedSlice<u128>>,
+    {
+        self.execute::<operation::LookupAccounts>(ids)
+    }
+
+    pub fn lookup_transfers<T>(&self, ids: T) -> Result<Result<Vec<Transfer>, SendError>, Elapsed>
+    where
+        T: Into<SendOwnedSlice<u128>>,
+    {
+        self.execute::<operation::LookupTransfers>(ids)
+    }
+
+    pub fn query_accounts<T>(&self, filter: T) -> Result<Result<Vec<Account>, SendError>, Elapsed>
+    where
+        T: RawConstPtr<Target = QueryFilter> + Send + 'static,
+    {
+        self.execute::<operation::QueryAccounts>(SendOwnedSlice::from_single(filter))
+    }
+
+    pub fn query_transfers<T>(&self, filter: T) -> Result<Result<Vec<Transfer>, SendError>, Elapsed>
+    where
+        T: RawConstPtr<Target = QueryFilter> + Send + 'static,
+    {
+        self.execute::<operation::QueryTransfers>(SendOwnedSlice::from_single(filter))
+    }
+}
+
+impl<T> Default for Slot<T> {
+    fn default() -> Self {
+        Self {
+            reply: Mutex::new(None),
+            ready: Condvar::new(),
+        }
+    }
+}
+
+impl<T> Slot<T> {
+    /// Blocks the current thread until the reply is received.
+    fn wait(&self) -> T {
+        let reply = self.reply.lock().unwrap();
+        let mut reply = self.ready.wait_while(reply, |r| r.is_none()).unwrap();
+        // PANIC: Unwrapping is OK here, because we've waited until the `reply` is `Some`.
+        reply.take().unwrap()
+    }
+
+    /// Blocks the current thread until the reply is received, or the `timeout` elapses.
+    fn wait_timeout(&self, timeout: Duration) -> Result<T, Elapsed> {
+        let reply = self.reply.lock().unwrap();
+        let (mut reply, _) = self
+            .ready
+            .wait_timeout_while(reply, timeout, |r| r.is_none())
+            .unwrap();
+        reply.take().ok_or(Elapsed(()))
+    }
+
+    fn complete(&self, reply: T) {
+        *self.reply.lock().unwrap() = Some(reply);
+        self.ready.notify_one();
+    }
+}
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        "Request timed out".fmt(f)
+    }
+}
+
+impl Error for Elapsed {}
+
+fn _test_thread_safe(client: Client, ids: Vec<u128>) {
+    check_thread_safe(client.execute::<operation::LookupAccounts>(ids));
+    check_thread_safe(client);
+
+    fn check_thread_safe<T>(_: T)
+    where
+        T: Send + Sync + 'static,
+    {
+    }
+}
+
+#[cfg(test)]
+mod slot_spec {
+    use std::{sync::Arc, thread, time::Duration};
+
+    use super::{Elapsed, Slot};
+
+    #[test]
+    fn wait_timeout_elapses_without_reply() {
+        let slot = Slot::<()>::default();
+
+        assert!(matches!(
+            slot.wait_timeout(Duration::from_millis(1)),
+            Err(Elapsed(())),
+        ));
+    }
+
+    #[test]
+    fn wait_returns_reply_completed_from_another_thread() {
+        let slot = Arc::new(Slot::default());
+
+        let completer = thread::spawn({
+            let slot = Arc::clone(&slot);
+            move || {
+                thread::sleep(Duration::from_millis(1));
+                slot.complete(Ok::<_, ()>(vec![1_u128]));
+            }
+        });
+
+        assert_eq!(slot.wait(), Ok(vec![1]));
+        completer.join().unwrap();
+    }
+}
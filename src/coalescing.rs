@@ -0,0 +1,249 @@
+//! Version of the high-level [`Client`] coalescing concurrent lookups of the same [`Account`]s.
+//!
+//! [`Client`]: crate::Client
+
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    mem,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use core::{
+    error::{CreateTransfersError, SendError},
+    util::SendOwnedSlice,
+};
+use tokio::sync::oneshot;
+
+use crate::{Account, Transfer};
+
+/// [TigerBeetle] client coalescing concurrent lookups of the same [`Account`]s.
+///
+/// Concurrent [`Client::lookup_accounts()`] calls with overlapping IDs are merged, so every ID is
+/// requested only once, and the result is fanned out to all the waiters.
+///
+/// Optionally, found [`Account`]s are cached for a short time (see [`Client::with_cache()`]). The
+/// cache is invalidated for [`Account`]s touched by [`Client::create_transfers()`] of this
+/// [`Client`], but not by any other client.
+///
+/// [TigerBeetle]: https://tigerbeetle.com
+pub struct Client {
+    inner: crate::Client,
+    state: Mutex<State>,
+    cache: Option<CacheConfig>,
+    /// Number of invalidations happened, so the [`Account`]s looked up concurrently with them
+    /// are not cached.
+    epoch: AtomicU64,
+}
+
+#[derive(Clone, Copy)]
+struct CacheConfig {
+    ttl: Duration,
+    capacity: usize,
+}
+
+#[derive(Default)]
+struct State {
+    /// Waiters of the [`Account`]s being looked up.
+    pending: HashMap<u128, Vec<Waiter>>,
+    cached: HashMap<u128, (Account, Instant)>,
+}
+
+type Waiter = oneshot::Sender<Result<Option<Account>, SendError>>;
+
+/// Way a single ID is looked up.
+enum Lookup {
+    /// Found in the cache.
+    Cached(Account),
+    /// Fetched by this lookup.
+    Fetched,
+    /// Fetched by a concurrent lookup.
+    Waiting(oneshot::Receiver<Result<Option<Account>, SendError>>),
+}
+
+impl Client {
+    /// Wraps the provided [`crate::Client`] without caching looked up [`Account`]s.
+    pub fn new(inner: crate::Client) -> Self {
+        Self {
+            inner,
+            state: Mutex::default(),
+            cache: None,
+            epoch: AtomicU64::new(0),
+        }
+    }
+
+    /// Wraps the provided [`crate::Client`], caching at most `capacity` looked up [`Account`]s
+    /// for the `ttl` duration.
+    pub fn with_cache(inner: crate::Client, ttl: Duration, capacity: usize) -> Self {
+        Self {
+            cache: Some(CacheConfig { ttl, capacity }),
+            ..Self::new(inner)
+        }
+    }
+
+    /// Returns the wrapped [`crate::Client`] to perform other operations with.
+    pub fn inner(&self) -> &crate::Client {
+        &self.inner
+    }
+
+    /// Looks up [`Account`]s with the provided `ids`, joining any concurrent lookups of the same
+    /// IDs.
+    ///
+    /// Found [`Account`]s are returned in the order of the `ids`.
+    pub async fn lookup_accounts<T>(&self, ids: T) -> Result<Vec<Account>, SendError>
+    where
+        T: Into<SendOwnedSlice<u128>>,
+    {
+        let ids = ids.into();
+        let (lookups, to_fetch) = self.prepare(ids.as_slice());
+        let fetched = if to_fetch.is_empty() {
+            HashMap::new()
+        } else {
+            self.fetch(to_fetch).await?
+        };
+
+        let mut resolved = HashMap::with_capacity(lookups.len());
+        for (id, lookup) in lookups {
+            let account = match lookup {
+                Lookup::Cached(account) => Some(account),
+                Lookup::Fetched => fetched.get(&id).copied(),
+                Lookup::Waiting(rx) => match rx.await {
+                    Ok(result) => result?,
+                    // The lookup we've been waiting for was cancelled, so do it on our own.
+                    Err(_) => self.inner.lookup_accounts(vec![id]).await?.pop(),
+                },
+            };
+            resolved.insert(id, account);
+        }
+        // PANIC: Indexing is OK here, because every ID is resolved.
+        Ok(ids
+            .as_slice()
+            .iter()
+            .filter_map(|id| resolved[id])
+            .collect())
+    }
+
+    /// Creates the provided `transfers`, invalidating the cached [`Account`]s touched by them.
+    pub async fn create_transfers<T>(&self, transfers: T) -> Result<(), CreateTransfersError>
+    where
+        T: Into<SendOwnedSlice<Transfer>>,
+    {
+        let transfers = transfers.into();
+        let touched = transfers
+            .as_slice()
+            .iter()
+            .flat_map(|t| [t.debit_account_id(), t.credit_account_id()])
+            .collect::<Vec<_>>();
+        let result = self.inner.create_transfers(transfers).await;
+        if self.cache.is_some() {
+            let mut state = self.state.lock().unwrap();
+            self.epoch.fetch_add(1, Ordering::Relaxed);
+            for id in touched {
+                state.cached.remove(&id);
+            }
+        }
+        result
+    }
+
+    /// Resolves the provided `ids` from the cache, or joins the pending lookups of them, returning
+    /// the remaining IDs to fetch.
+    fn prepare(&self, ids: &[u128]) -> (HashMap<u128, Lookup>, Vec<u128>) {
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+        let State { pending, cached } = &mut *state;
+        let mut lookups = HashMap::with_capacity(ids.len());
+        let mut to_fetch = Vec::new();
+        for &id in ids {
+            let Entry::Vacant(lookup) = lookups.entry(id) else {
+                continue;
+            };
+            if let Some((account, _)) = cached.get(&id).filter(|(_, exp)| *exp > now) {
+                lookup.insert(Lookup::Cached(*account));
+            } else if let Some(waiters) = pending.get_mut(&id) {
+                let (tx, rx) = oneshot::channel();
+                waiters.push(tx);
+                lookup.insert(Lookup::Waiting(rx));
+            } else {
+                pending.insert(id, Vec::new());
+                to_fetch.push(id);
+                lookup.insert(Lookup::Fetched);
+            }
+        }
+        (lookups, to_fetch)
+    }
+
+    /// Fetches the provided pending `ids`, notifying all their waiters.
+    async fn fetch(&self, ids: Vec<u128>) -> Result<HashMap<u128, Account>, SendError> {
+        /// Removes the pending `ids` if the lookup is cancelled, so their waiters do it on their
+        /// own.
+        struct Guard<'c> {
+            client: &'c Client,
+            ids: Vec<u128>,
+        }
+
+        impl Drop for Guard<'_> {
+            fn drop(&mut self) {
+                let mut state = self.client.state.lock().unwrap();
+                for id in &self.ids {
+                    state.pending.remove(id);
+                }
+            }
+        }
+
+        let epoch = self.epoch.load(Ordering::Relaxed);
+        let mut guard = Guard {
+            client: self,
+            ids: ids.clone(),
+        };
+        let result = self.inner.lookup_accounts(ids).await.map(|found| {
+            found
+                .into_iter()
+                .map(|a| (a.id(), a))
+                .collect::<HashMap<_, _>>()
+        });
+        let ids = mem::take(&mut guard.ids);
+        drop(guard);
+
+        let mut state = self.state.lock().unwrap();
+        for id in &ids {
+            let account = result.as_ref().map(|found| found.get(id).copied());
+            for waiter in state.pending.remove(id).into_iter().flatten() {
+                // Waiter may be gone due to the `Future` cancellation, so the `.send()` error
+                // should be ignored.
+                waiter.send(account.map_err(|e| *e)).unwrap_or_else(drop);
+            }
+        }
+        if let (Some(cache), Ok(found)) = (self.cache, &result) {
+            // Don't cache the `Account`s possibly touched while being looked up.
+            if self.epoch.load(Ordering::Relaxed) == epoch {
+                let now = Instant::now();
+                state.cached.retain(|_, (_, exp)| *exp > now);
+                for account in found.values() {
+                    if state.cached.len() >= cache.capacity {
+                        break;
+                    }
+                    state
+                        .cached
+                        .insert(account.id(), (*account, now + cache.ttl));
+                }
+            }
+        }
+        result
+    }
+}
+
+fn _test_thread_safe(client: Client, ids: Vec<u128>, transfers: Vec<Transfer>) {
+    check_thread_safe(async move {
+        client.lookup_accounts(ids).await.unwrap();
+        client.create_transfers(transfers).await.unwrap();
+    });
+
+    fn check_thread_safe<T>(_: T)
+    where
+        T: Send + Sync + 'static,
+    {
+    }
+}
@@ -0,0 +1,234 @@
+//! Lookup of pending [`Transfer`]s about to expire.
+
+use std::{collections::HashSet, error::Error, fmt, time::Duration, time::SystemTime};
+
+use core::error::{SendError, ValidationError};
+
+use crate::{account, fan_out::REPLY_CAPACITY, operation, transfer, Client, Timestamp, Transfer};
+
+/// Error of getting expiring pending [`Transfer`]s.
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub enum ExpiryError {
+    /// Provided account ID is invalid.
+    Validation(ValidationError),
+    Send(SendError),
+}
+
+/// Pending [`Transfer`]s expiring within a window, collected page by page.
+struct Expiring {
+    /// Start of the window (exclusive).
+    now: SystemTime,
+    /// End of the window (inclusive), if representable.
+    deadline: Option<SystemTime>,
+    /// Pending [`Transfer`]s expiring within the window, along with their expiry.
+    pending: Vec<(SystemTime, Transfer)>,
+    /// IDs of the pending [`Transfer`]s already posted or voided.
+    resolved: HashSet<u128>,
+}
+
+impl Client {
+    /// Gets pending [`Transfer`]s of the [`Account`] with the provided `account_id`, which are
+    /// neither posted nor voided yet, and expire within the provided `window` from now.
+    ///
+    /// All the [`Transfer`]s of the [`Account`] are paginated, so this may issue multiple
+    /// requests, but only the pending ones expiring within the `window` are kept from every page
+    /// (along with the IDs of the posted or voided ones). Expiry is computed with
+    /// [`Transfer::expires_at()`] against the local clock, so may deviate from the cluster's one.
+    /// The results are in the expiry order.
+    ///
+    /// Fails with [`ExpiryError::Validation`] if the `account_id` is `u128::MAX`.
+    ///
+    /// [`Account`]: crate::Account
+    pub async fn get_expiring_pending_transfers(
+        &self,
+        account_id: u128,
+        window: Duration,
+    ) -> Result<Vec<Transfer>, ExpiryError> {
+        let filter = filter(account_id)?;
+        let mut expiring = Expiring::new(SystemTime::now(), window);
+        self.for_each_page::<operation::GetAccountTransfers, _>(
+            filter,
+            |t| t.as_raw().timestamp,
+            |page| expiring.extend(page),
+        )
+        .await?;
+        Ok(expiring.finish())
+    }
+}
+
+/// Creates an [`account::Filter`] querying all the [`Transfer`]s of the [`Account`] with the
+/// provided `account_id`.
+///
+/// [`Account`]: crate::Account
+fn filter(account_id: u128) -> Result<account::Filter, ValidationError> {
+    Ok(account::Filter::try_new(account_id, REPLY_CAPACITY)?
+        .with_flags(account::FilterFlags::DEBITS | account::FilterFlags::CREDITS))
+}
+
+impl Expiring {
+    /// Creates a new empty [`Expiring`] collection for the `window` starting `now`.
+    fn new(now: SystemTime, window: Duration) -> Self {
+        Self {
+            now,
+            deadline: now.checked_add(window),
+            pending: Vec::new(),
+            resolved: HashSet::new(),
+        }
+    }
+
+    /// Keeps the pending [`Transfer`]s of the provided `page` expiring within the window, and the
+    /// IDs of the pending [`Transfer`]s it posts or voids.
+    fn extend(&mut self, page: Vec<Transfer>) {
+        for t in page {
+            if t.flags().intersects(
+                transfer::Flags::POST_PENDING_TRANSFER | transfer::Flags::VOID_PENDING_TRANSFER,
+            ) {
+                _ = self.resolved.insert(t.pending_id());
+            } else if let Some(at) = t.expires_at().map(Timestamp::to_system_time) {
+                if at > self.now && self.deadline.map_or(true, |d| at <= d) {
+                    self.pending.push((at, t));
+                }
+            }
+        }
+    }
+
+    /// Returns the kept pending [`Transfer`]s not posted or voided, in the expiry order.
+    fn finish(self) -> Vec<Transfer> {
+        let Self {
+            mut pending,
+            resolved,
+            ..
+        } = self;
+        pending.retain(|(_, t)| !resolved.contains(&t.id()));
+        pending.sort_by_key(|(at, _)| *at);
+        pending.into_iter().map(|(_, t)| t).collect()
+    }
+}
+
+impl Error for ExpiryError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(match self {
+            Self::Validation(e) => e as _,
+            Self::Send(e) => e as _,
+        })
+    }
+}
+
+impl fmt::Display for ExpiryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Failed to get expiring pending transfers: ")?;
+        match self {
+            Self::Validation(e) => write!(f, "{e}"),
+            Self::Send(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<ValidationError> for ExpiryError {
+    fn from(value: ValidationError) -> Self {
+        Self::Validation(value)
+    }
+}
+
+impl From<SendError> for ExpiryError {
+    fn from(value: SendError) -> Self {
+        Self::Send(value)
+    }
+}
+
+fn _test_thread_safe(client: Client, account_id: u128, window: Duration) {
+    check_thread_safe(async move {
+        client
+            .get_expiring_pending_transfers(account_id, window)
+            .await
+            .unwrap();
+    });
+
+    fn check_thread_safe<T>(_: T)
+    where
+        T: Send + Sync + 'static,
+    {
+    }
+}
+
+#[cfg(test)]
+mod expiry_spec {
+    use std::time::{Duration, SystemTime};
+
+    use core::error::ValidationError;
+
+    use crate::{account, fan_out::REPLY_CAPACITY, transfer, Timestamp, Transfer};
+
+    use super::{filter, Expiring};
+
+    /// Creates a pending [`Transfer`] created at the `created` second, with the `timeout`.
+    fn pending(id: u128, created: u64, timeout: u32) -> Transfer {
+        Transfer::new(id)
+            .with_flags(transfer::Flags::PENDING)
+            .with_timeout(timeout)
+            .with_timestamp(Timestamp::from_nanos(created * 1_000_000_000))
+    }
+
+    fn at(secs: u64) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn rejects_invalid_account_id() {
+        assert_eq!(
+            filter(u128::MAX).err(),
+            Some(ValidationError::Max {
+                field: "account id",
+            }),
+        );
+
+        let filter = filter(1).unwrap();
+        assert_eq!(filter.account_id(), 1);
+        assert_eq!(filter.limit(), REPLY_CAPACITY);
+        assert_eq!(
+            filter.flags(),
+            account::FilterFlags::DEBITS | account::FilterFlags::CREDITS,
+        );
+    }
+
+    #[test]
+    fn keeps_only_pending_expiring_within_window() {
+        let mut expiring = Expiring::new(at(100), Duration::from_secs(60));
+
+        let single_phase = Transfer::new(5).with_timestamp(Timestamp::from_nanos(1));
+        expiring.extend(vec![
+            pending(1, 10, 50),  // expired at 60
+            pending(2, 90, 40),  // expires at 130
+            pending(3, 90, 100), // expires at 190, past the window
+            pending(4, 50, 0),   // never expires
+            single_phase,        // not pending
+        ]);
+        expiring.extend(vec![pending(6, 95, 10)]); // expires at 105
+
+        assert_eq!(expiring.pending.len(), 2);
+        let ids = expiring
+            .finish()
+            .iter()
+            .map(Transfer::id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, [6, 2]);
+    }
+
+    #[test]
+    fn drops_pending_resolved_on_later_pages() {
+        let mut expiring = Expiring::new(at(100), Duration::from_secs(60));
+
+        expiring.extend(vec![pending(1, 90, 20), pending(2, 90, 30)]);
+        expiring.extend(vec![Transfer::new(3)
+            .with_flags(transfer::Flags::POST_PENDING_TRANSFER)
+            .with_pending_id(1)]);
+
+        let ids = expiring
+            .finish()
+            .iter()
+            .map(Transfer::id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, [2]);
+    }
+}
@@ -0,0 +1,270 @@
+//! Queries over multiple [`Account`]s at once.
+//!
+//! [`Account`]: crate::Account
+
+use std::collections::HashMap;
+
+use core::{error::SendError, util::SendOwnedSlice};
+
+use crate::{account, join::join_all, operation, Client, Operation, Timestamp, Transfer};
+
+/// Maximum number of [`Transfer`]s or [`account::Balance`]s fitting into a single reply of the
+/// cluster, as a query limit.
+pub(crate) const REPLY_CAPACITY: u32 = operation::MESSAGE_CAPACITY as u32;
+
+impl Client {
+    /// Gets [`Transfer`]s of all the [`Account`]s with the provided `account_ids`, matching the
+    /// provided `filter` (its [`account::Filter::account_id()`] is ignored).
+    ///
+    /// Accounts are queried concurrently, each one paginated by the `filter`'s limit (capped by
+    /// the reply capacity of the cluster) until its [`Transfer`]s are exhausted. The results are
+    /// merged in the timestamp order (reversed, if [`account::FilterFlags::REVERSED`] is set), with
+    /// every [`Transfer`] between two of the [`Account`]s returned once.
+    ///
+    /// The results are collected eagerly rather than streamed, as there is no [`Stream`] trait in
+    /// `std` to expose yet. Narrow the `filter`'s timestamp range to bound the memory used.
+    ///
+    /// [`Account`]: crate::Account
+    /// [`Stream`]: https://docs.rs/futures/latest/futures/stream/trait.Stream.html
+    pub async fn get_accounts_transfers<I>(
+        &self,
+        account_ids: I,
+        filter: account::Filter,
+    ) -> Result<Vec<Transfer>, SendError>
+    where
+        I: IntoIterator<Item = u128>,
+    {
+        let per_account = self
+            .get_all_per_account::<operation::GetAccountTransfers, _>(account_ids, filter, |t| {
+                t.as_raw().timestamp
+            })
+            .await?;
+        Ok(merge(per_account, is_reversed(&filter)))
+    }
+
+    /// Gets historical [`account::Balance`]s of all the [`Account`]s with the provided
+    /// `account_ids`, matching the provided `filter` (its [`account::Filter::account_id()`] is
+    /// ignored), grouped by the account ID.
+    ///
+    /// Accounts are queried concurrently, each one paginated by the `filter`'s limit (capped by
+    /// the reply capacity of the cluster) until its [`account::Balance`]s are exhausted.
+    ///
+    /// The results are collected eagerly rather than streamed, as there is no [`Stream`] trait in
+    /// `std` to expose yet. Narrow the `filter`'s timestamp range to bound the memory used.
+    ///
+    /// [`Account`]: crate::Account
+    /// [`Stream`]: https://docs.rs/futures/latest/futures/stream/trait.Stream.html
+    pub async fn get_accounts_balances<I>(
+        &self,
+        account_ids: I,
+        filter: account::Filter,
+    ) -> Result<HashMap<u128, Vec<account::Balance>>, SendError>
+    where
+        I: IntoIterator<Item = u128>,
+    {
+        self.get_all_per_account::<operation::GetAccountBalances, _>(account_ids, filter, |b| {
+            b.as_raw().timestamp
+        })
+        .await
+    }
+
+    /// Executes the provided [`Operation`] for every of the `account_ids` concurrently, paginating
+    /// until all the results are received.
+    async fn get_all_per_account<Op, T>(
+        &self,
+        account_ids: impl IntoIterator<Item = u128>,
+        filter: account::Filter,
+        timestamp: fn(&T) -> u64,
+    ) -> Result<HashMap<u128, Vec<T>>, SendError>
+    where
+        Op: Operation<Input = SendOwnedSlice<account::Filter>, Output = Vec<T>, Error = SendError>
+            + 'static,
+    {
+        let mut account_ids = account_ids.into_iter().collect::<Vec<_>>();
+        // No `Account` can have the reserved `u128::MAX` ID, so nothing to query for it.
+        account_ids.retain(|id| *id != u128::MAX);
+        account_ids.sort_unstable();
+        account_ids.dedup();
+        let requests = account_ids
+            .iter()
+            .map(|id| self.get_all::<Op, _>(filter.with_account_id(*id), timestamp))
+            .collect::<Vec<_>>();
+        account_ids
+            .into_iter()
+            .zip(join_all(requests).await)
+            .map(|(id, res)| res.map(|all| (id, all)))
+            .collect()
+    }
+
+    /// Executes the provided [`Operation`] with the `filter` page by page, until all the results
+    /// are received.
+    async fn get_all<Op, T>(
+        &self,
+        filter: account::Filter,
+        timestamp: fn(&T) -> u64,
+    ) -> Result<Vec<T>, SendError>
+    where
+        Op: Operation<Input = SendOwnedSlice<account::Filter>, Output = Vec<T>, Error = SendError>
+            + 'static,
+    {
+        let mut all = Vec::new();
+        self.for_each_page::<Op, _>(filter, timestamp, |page| all.extend(page))
+            .await?;
+        Ok(all)
+    }
+
+    /// Executes the provided [`Operation`] with the `filter` page by page, passing every received
+    /// page to the provided `on_page` function, until all the results are received.
+    ///
+    /// The `filter`'s limit is capped by the [`REPLY_CAPACITY`], as the cluster never replies with
+    /// more results anyway.
+    pub(crate) async fn for_each_page<Op, T>(
+        &self,
+        mut filter: account::Filter,
+        timestamp: fn(&T) -> u64,
+        mut on_page: impl FnMut(Vec<T>),
+    ) -> Result<(), SendError>
+    where
+        Op: Operation<Input = SendOwnedSlice<account::Filter>, Output = Vec<T>, Error = SendError>
+            + 'static,
+    {
+        filter.as_raw_mut().limit = filter.limit().min(REPLY_CAPACITY);
+        loop {
+            let page = self
+                .execute::<Op>(SendOwnedSlice::from_single(Box::new(filter)))
+                .await?;
+            let has_next = next_page(&mut filter, page.len(), page.last().map(timestamp));
+            on_page(page);
+            if !has_next {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Merges [`Transfer`]s of multiple [`Account`]s in the timestamp order (reversed, if
+/// `reversed`), returning every [`Transfer`] between two of the [`Account`]s once.
+///
+/// [`Account`]: crate::Account
+fn merge(per_account: HashMap<u128, Vec<Transfer>>, reversed: bool) -> Vec<Transfer> {
+    let mut transfers = per_account.into_values().flatten().collect::<Vec<_>>();
+    if reversed {
+        transfers.sort_by_key(|t| std::cmp::Reverse(t.as_raw().timestamp));
+    } else {
+        transfers.sort_by_key(|t| t.as_raw().timestamp);
+    }
+    // The same `Transfer` has the same unique timestamp, so its copies are adjacent.
+    transfers.dedup_by_key(|t| t.id());
+    transfers
+}
+
+/// Advances the provided `filter` past the received page of `len` results with the `last`
+/// timestamp.
+///
+/// Returns `false` if there are no more results to query, either because the page is not full,
+/// or because no valid timestamp remains past the `last` one.
+fn next_page(filter: &mut account::Filter, len: usize, last: Option<u64>) -> bool {
+    let Some(last) = last else {
+        return false;
+    };
+    if len < filter.limit() as usize {
+        return false;
+    }
+    if is_reversed(filter) {
+        // Zero `timestamp_max` means "unset", so cannot be used to query before the first one.
+        let Some(max) = last.checked_sub(1).and_then(Timestamp::from_nanos) else {
+            return false;
+        };
+        filter.set_timestamp_max(Some(max));
+    } else {
+        let Some(min) = last.checked_add(1).and_then(Timestamp::from_nanos) else {
+            return false;
+        };
+        filter.set_timestamp_min(Some(min));
+    }
+    true
+}
+
+fn is_reversed(filter: &account::Filter) -> bool {
+    filter.flags().contains(account::FilterFlags::REVERSED)
+}
+
+fn _test_thread_safe(client: Client, account_ids: Vec<u128>, filter: account::Filter) {
+    check_thread_safe(async move {
+        client
+            .get_accounts_transfers(account_ids.clone(), filter)
+            .await
+            .unwrap();
+        client
+            .get_accounts_balances(account_ids, filter)
+            .await
+            .unwrap();
+    });
+
+    fn check_thread_safe<T>(_: T)
+    where
+        T: Send + Sync + 'static,
+    {
+    }
+}
+
+#[cfg(test)]
+mod fan_out_spec {
+    use std::collections::HashMap;
+
+    use crate::{account, Timestamp, Transfer};
+
+    use super::{merge, next_page};
+
+    fn transfer(id: u128, timestamp: u64) -> Transfer {
+        Transfer::new(id).with_timestamp(Timestamp::from_nanos(timestamp))
+    }
+
+    #[test]
+    fn merges_in_timestamp_order_without_duplicates() {
+        let per_account = HashMap::from([
+            (1, vec![transfer(10, 1), transfer(12, 3)]),
+            (2, vec![transfer(10, 1), transfer(11, 2)]),
+        ]);
+
+        let ids = |ts: Vec<Transfer>| ts.iter().map(Transfer::id).collect::<Vec<_>>();
+
+        assert_eq!(ids(merge(per_account.clone(), false)), [10, 11, 12]);
+        assert_eq!(ids(merge(per_account, true)), [12, 11, 10]);
+    }
+
+    #[test]
+    fn advances_past_full_page() {
+        let mut filter = account::Filter::new(1, 2);
+
+        assert!(next_page(&mut filter, 2, Some(5)));
+        assert_eq!(filter.timestamp_min(), Timestamp::from_nanos(6));
+
+        let mut filter = account::Filter::new(1, 2).with_flags(account::FilterFlags::REVERSED);
+
+        assert!(next_page(&mut filter, 2, Some(5)));
+        assert_eq!(filter.timestamp_max(), Timestamp::from_nanos(4));
+    }
+
+    #[test]
+    fn stops_on_partial_or_empty_page() {
+        let mut filter = account::Filter::new(1, 2);
+
+        assert!(!next_page(&mut filter, 1, Some(5)));
+        assert!(!next_page(&mut filter, 0, None));
+        assert_eq!(filter.timestamp_min(), None);
+    }
+
+    #[test]
+    fn stops_past_timestamp_range() {
+        let mut filter = account::Filter::new(1, 1).with_flags(account::FilterFlags::REVERSED);
+
+        assert!(!next_page(&mut filter, 1, Some(1)));
+        assert_eq!(filter.timestamp_max(), None);
+
+        let mut filter = account::Filter::new(1, 1);
+
+        assert!(!next_page(&mut filter, 1, Some(u64::MAX - 1)));
+        assert_eq!(filter.timestamp_min(), None);
+    }
+}
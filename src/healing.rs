@@ -0,0 +1,247 @@
+//! Self-healing version of the high-level [`Client`], recreating its session once evicted by the
+//! cluster.
+//!
+//! [`Client`]: crate::Client
+
+use std::{
+    num::NonZeroUsize,
+    sync::{Arc, RwLock},
+};
+
+use core::{
+    error::{CreateAccountsError, CreateTransfersError, NewClientError, SendError},
+    util::{RawConstPtr, SendOwnedSlice},
+};
+use tokio::sync::Mutex;
+
+use crate::{account, operation, Account, Operation, QueryFilter, Transfer};
+
+/// Self-healing [TigerBeetle] client.
+///
+/// Provides the same operations as the [`crate::Client`], but once the underlying session is
+/// evicted by the cluster (see [`crate::Client::is_evicted()`]), transparently recreates it with
+/// the same cluster ID and addresses.
+///
+/// Idempotent operations (lookups and queries) failed due to the eviction are retried once on the
+/// recreated session, while creates still return the eviction error, because whether to retry
+/// them is the caller's decision.
+///
+/// A session evicted because of its release being incompatible with the cluster's one (see
+/// [`crate::Client::incompatible_release()`]) is not recreated, as a new one would be evicted the
+/// same way, so the eviction error is returned as is.
+///
+/// [TigerBeetle]: https://tigerbeetle.com
+pub struct Client {
+    cluster_id: u128,
+    address: Vec<u8>,
+    max_in_flight: Option<NonZeroUsize>,
+    current: RwLock<Arc<crate::Client>>,
+    /// Serializes recreation of the evicted sessions.
+    healing: Mutex<()>,
+}
+
+impl Client {
+    pub fn new<A>(cluster_id: u128, address: A) -> Result<Self, NewClientError>
+    where
+        A: AsRef<[u8]>,
+    {
+        Self::with_options(cluster_id, address.as_ref().to_vec(), None)
+    }
+
+    /// Creates a new [`Client`] with every its session allowing at most `max_in_flight` requests
+    /// in flight.
+    ///
+    /// See [`crate::Client::with_max_in_flight()`] for details.
+    pub fn with_max_in_flight<A>(
+        cluster_id: u128,
+        address: A,
+        max_in_flight: NonZeroUsize,
+    ) -> Result<Self, NewClientError>
+    where
+        A: AsRef<[u8]>,
+    {
+        Self::with_options(cluster_id, address.as_ref().to_vec(), Some(max_in_flight))
+    }
+
+    fn with_options(
+        cluster_id: u128,
+        address: Vec<u8>,
+        max_in_flight: Option<NonZeroUsize>,
+    ) -> Result<Self, NewClientError> {
+        let client = connect(cluster_id, &address, max_in_flight)?;
+        Ok(Self {
+            cluster_id,
+            address,
+            max_in_flight,
+            current: RwLock::new(Arc::new(client)),
+            healing: Mutex::new(()),
+        })
+    }
+
+    /// Executes the provided [`Operation`] with the provided `input`, recreating the session if
+    /// it's evicted.
+    ///
+    /// The failed request is not retried.
+    pub async fn execute<Op>(&self, input: impl Into<Op::Input>) -> Result<Op::Output, Op::Error>
+    where
+        Op: Operation + 'static,
+    {
+        let client = self.current();
+        let result = client.execute::<Op>(input).await;
+        if result.is_err() && needs_healing(&client) {
+            self.heal(&client).await;
+        }
+        result
+    }
+
+    pub async fn create_accounts<T>(&self, accounts: T) -> Result<(), CreateAccountsError>
+    where
+        T: Into<SendOwnedSlice<Account>>,
+    {
+        self.execute::<operation::CreateAccounts>(accounts).await
+    }
+
+    pub async fn create_transfers<T>(&self, transfers: T) -> Result<(), CreateTransfersError>
+    where
+        T: Into<SendOwnedSlice<Transfer>>,
+    {
+        self.execute::<operation::CreateTransfers>(transfers).await
+    }
+
+    pub async fn get_account_balances<T>(
+        &self,
+        filter: T,
+    ) -> Result<Vec<account::Balance>, SendError>
+    where
+        T: RawConstPtr<Target = account::Filter> + Send + 'static,
+    {
+        self.execute_idempotent::<operation::GetAccountBalances, _>(SendOwnedSlice::from_single(
+            filter,
+        ))
+        .await
+    }
+
+    pub async fn get_account_transfers<T>(&self, filter: T) -> Result<Vec<Transfer>, SendError>
+    where
+        T: RawConstPtr<Target = account::Filter> + Send + 'static,
+    {
+        self.execute_idempotent::<operation::GetAccountTransfers, _>(SendOwnedSlice::from_single(
+            filter,
+        ))
+        .await
+    }
+
+    pub async fn lookup_accounts<T>(&self, ids: T) -> Result<Vec<Account>, SendError>
+    where
+        T: Into<SendOwnedSlice<u128>>,
+    {
+        self.execute_idempotent::<operation::LookupAccounts, _>(ids.into())
+            .await
+    }
+
+    pub async fn lookup_transfers<T>(&self, ids: T) -> Result<Vec<Transfer>, SendError>
+    where
+        T: Into<SendOwnedSlice<u128>>,
+    {
+        self.execute_idempotent::<operation::LookupTransfers, _>(ids.into())
+            .await
+    }
+
+    pub async fn query_accounts<T>(&self, filter: T) -> Result<Vec<Account>, SendError>
+    where
+        T: RawConstPtr<Target = QueryFilter> + Send + 'static,
+    {
+        self.execute_idempotent::<operation::QueryAccounts, _>(SendOwnedSlice::from_single(filter))
+            .await
+    }
+
+    pub async fn query_transfers<T>(&self, filter: T) -> Result<Vec<Transfer>, SendError>
+    where
+        T: RawConstPtr<Target = QueryFilter> + Send + 'static,
+    {
+        self.execute_idempotent::<operation::QueryTransfers, _>(SendOwnedSlice::from_single(filter))
+            .await
+    }
+
+    /// Executes the provided idempotent [`Operation`], retrying it once on the recreated session
+    /// if the current one is evicted.
+    ///
+    /// The `input` is shared between the attempts without copying.
+    async fn execute_idempotent<Op, T>(
+        &self,
+        input: SendOwnedSlice<T>,
+    ) -> Result<Op::Output, Op::Error>
+    where
+        Op: Operation<Input = SendOwnedSlice<T>> + 'static,
+        T: Sync + 'static,
+    {
+        let input = Arc::new(input);
+        let client = self.current();
+        match client
+            .execute::<Op>(SendOwnedSlice::from_shared(Arc::clone(&input)))
+            .await
+        {
+            Err(_) if needs_healing(&client) => {
+                self.heal(&client).await;
+                self.current()
+                    .execute::<Op>(SendOwnedSlice::from_shared(input))
+                    .await
+            }
+            result => result,
+        }
+    }
+
+    fn current(&self) -> Arc<crate::Client> {
+        Arc::clone(&self.current.read().unwrap())
+    }
+
+    /// Replaces the `evicted` session with a new one, unless it's replaced already.
+    ///
+    /// If the new session cannot be created, the `evicted` one is kept to be replaced by the
+    /// next failed request.
+    ///
+    /// The new session is created without holding the lock of the current one, so other
+    /// requests are not blocked meanwhile.
+    async fn heal(&self, evicted: &Arc<crate::Client>) {
+        let _healing = self.healing.lock().await;
+        if !Arc::ptr_eq(&self.current(), evicted) {
+            return;
+        }
+        if let Ok(client) = connect(self.cluster_id, &self.address, self.max_in_flight) {
+            // The `evicted` session is dropped once all its requests in flight complete.
+            *self.current.write().unwrap() = Arc::new(client);
+        }
+    }
+}
+
+/// Checks whether the provided `client` is evicted, and recreating it may help.
+fn needs_healing(client: &crate::Client) -> bool {
+    client.is_evicted() && client.incompatible_release().is_none()
+}
+
+fn connect(
+    cluster_id: u128,
+    address: &[u8],
+    max_in_flight: Option<NonZeroUsize>,
+) -> Result<crate::Client, NewClientError> {
+    match max_in_flight {
+        Some(max) => crate::Client::with_max_in_flight(cluster_id, address, max),
+        None => crate::Client::new(cluster_id, address),
+    }
+}
+
+fn _test_thread_safe(client: Client, ids: Vec<u128>) {
+    check_thread_safe(async move {
+        client.lookup_accounts(ids.clone()).await.unwrap();
+        client
+            .execute::<operation::LookupTransfers>(ids)
+            .await
+            .unwrap();
+    });
+
+    fn check_thread_safe<T>(_: T)
+    where
+        T: Send + Sync + 'static,
+    {
+    }
+}
@@ -0,0 +1,52 @@
+//! Concurrent polling of multiple [`Future`]s, without depending on an async runtime.
+
+use std::{
+    future::{self, Future},
+    pin::Pin,
+    task::Poll,
+};
+
+/// Polls all the provided `futures` concurrently, returning their outputs in the same order.
+pub(crate) async fn join_all<I>(futures: I) -> Vec<<I::Item as Future>::Output>
+where
+    I: IntoIterator,
+    I::Item: Future,
+{
+    let mut futures = futures
+        .into_iter()
+        .map(Box::pin)
+        .collect::<Vec<Pin<Box<_>>>>();
+    let mut outputs = futures.iter().map(|_| None).collect::<Vec<_>>();
+    future::poll_fn(|cx| {
+        let mut ready = true;
+        for (fut, out) in futures.iter_mut().zip(&mut outputs) {
+            if out.is_none() {
+                match fut.as_mut().poll(cx) {
+                    Poll::Ready(o) => *out = Some(o),
+                    Poll::Pending => ready = false,
+                }
+            }
+        }
+        if ready {
+            // PANIC: Unwrapping is OK here, because all the futures are ready.
+            Poll::Ready(outputs.drain(..).map(Option::unwrap).collect())
+        } else {
+            Poll::Pending
+        }
+    })
+    .await
+}
+
+#[cfg(test)]
+mod join_all_spec {
+    use std::future;
+
+    use super::join_all;
+
+    #[test]
+    fn preserves_order() {
+        let outputs = pollster::block_on(join_all((0..3).map(future::ready)));
+
+        assert_eq!(outputs, [0, 1, 2]);
+    }
+}
@@ -4,30 +4,78 @@
 )]
 #![forbid(unsafe_code)]
 
+pub mod blocking;
+pub mod coalescing;
+mod expiry;
+mod fan_out;
+pub mod healing;
 mod id;
-mod reply;
+mod join;
+mod limit;
+pub mod lookup;
+pub mod operation;
+mod pool;
+mod router;
+#[cfg(feature = "metrics")]
+mod telemetry;
+#[cfg(feature = "tracing")]
+mod trace;
 
+use std::{
+    future::Future,
+    mem,
+    num::NonZeroUsize,
+    ops::ControlFlow,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, OnceLock,
+    },
+    time::{Instant, SystemTime},
+};
+
+use bytemuck::Pod;
 use error::NewClientError;
-use reply::Reply;
 use tokio::sync::oneshot;
 
 use core::{
-    error::{CreateAccountsError, CreateTransfersError, SendError},
+    error::{
+        CheckReleaseError, CreateAccountsError, CreateTransfersError, IncompatibleReleaseError,
+        SendError, SendErrorKind,
+    },
     util::{RawConstPtr, SendAsBytesOwnedSlice, SendOwnedSlice},
 };
 
-pub use core::{self, account, error, transfer, Account, Packet, QueryFilter, Transfer};
+pub use core::{
+    self, account, error, transfer, Account, Packet, QueryFilter, Timestamp, Transfer,
+    TIGERBEETLE_COMMIT, TIGERBEETLE_RELEASE,
+};
 
-pub use self::id::id;
+pub use self::{
+    expiry::ExpiryError,
+    id::id,
+    limit::Overloaded,
+    operation::Operation,
+    pool::ClientPool,
+    router::{ClusterRouter, RouteError},
+};
+
+use self::limit::{Limit, Permit};
 
 pub struct Client {
     inner: core::Client<&'static Callbacks>,
+    limit: Limit,
+    /// Error this [`Client`] was evicted by the cluster with, if any.
+    eviction: Arc<OnceLock<SendError>>,
+    in_flight: Arc<AtomicUsize>,
 }
 
 struct Callbacks;
 
+/// Completion of a submitted request, called once its reply is received.
+type Completion = Box<dyn FnOnce(Result<core::Reply<'_>, SendError>) + Send>;
+
 struct UserData {
-    reply_sender: oneshot::Sender<Result<Reply, SendError>>,
+    complete: Completion,
     data: SendAsBytesOwnedSlice,
 }
 
@@ -38,41 +86,147 @@ impl Client {
     {
         Ok(Client {
             inner: core::Client::with_callback(cluster_id, address, &Callbacks)?,
+            limit: Limit::default(),
+            eviction: Arc::default(),
+            in_flight: Arc::default(),
+        })
+    }
+
+    /// Creates a new [`Client`] allowing at most `max_in_flight` requests to be submitted and not
+    /// completed yet at the same time.
+    ///
+    /// Requests over the limit wait until some of the requests in flight complete, while `try_`
+    /// methods fail fast with [`Overloaded`] instead.
+    ///
+    /// Values of `max_in_flight` above [`Semaphore::MAX_PERMITS`] are clamped to it.
+    ///
+    /// [`Semaphore::MAX_PERMITS`]: tokio::sync::Semaphore::MAX_PERMITS
+    pub fn with_max_in_flight<A>(
+        cluster_id: u128,
+        address: A,
+        max_in_flight: NonZeroUsize,
+    ) -> Result<Self, NewClientError>
+    where
+        A: AsRef<[u8]>,
+    {
+        Ok(Client {
+            inner: core::Client::with_callback(cluster_id, address, &Callbacks)?,
+            limit: Limit::new(max_in_flight),
+            eviction: Arc::default(),
+            in_flight: Arc::default(),
         })
     }
 
+    /// Indicates whether this [`Client`] was evicted by the cluster, so all its further requests
+    /// fail, and it should be recreated.
+    ///
+    /// Recreating doesn't help if the release of this [`Client`] is incompatible with the
+    /// cluster's one (see [`Client::incompatible_release()`]).
+    pub fn is_evicted(&self) -> bool {
+        self.eviction.get().is_some()
+    }
+
+    /// Returns the [`IncompatibleReleaseError`] if this [`Client`] was evicted by the cluster
+    /// because of its release (see [`TIGERBEETLE_RELEASE`]) being incompatible with the cluster's
+    /// one.
+    pub fn incompatible_release(&self) -> Option<IncompatibleReleaseError> {
+        self.eviction.get().and_then(|e| e.incompatible_release())
+    }
+
+    /// Checks the release of this [`Client`] (see [`TIGERBEETLE_RELEASE`]) to be compatible with
+    /// the cluster's one, by submitting an empty lookup.
+    ///
+    /// Errors of the other requests caused by the incompatible release are reported as
+    /// [`SendError`]s, with the [`IncompatibleReleaseError`] available via
+    /// [`SendError::incompatible_release()`] or as their [`Error::source()`].
+    ///
+    /// [`Error::source()`]: std::error::Error::source
+    pub async fn check_release(&self) -> Result<(), CheckReleaseError> {
+        // Typed methods don't submit empty inputs at all, so the raw one is used.
+        self.submit_raw(<operation::LookupAccounts as Operation>::CODE, Vec::new())
+            .await?;
+        Ok(())
+    }
+
+    /// Returns the number of requests submitted by this [`Client`] and not completed yet.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::Relaxed)
+    }
+
+    /// Executes the provided [`Operation`] with the provided `input`.
+    pub async fn execute<Op>(&self, input: impl Into<Op::Input>) -> Result<Op::Output, Op::Error>
+    where
+        Op: Operation + 'static,
+    {
+        let input = input.into();
+        traced(Op::CODE, async move {
+            let data = match Op::encode(input) {
+                ControlFlow::Continue(data) => data,
+                ControlFlow::Break(output) => return Ok(output),
+            };
+            let permit = self.acquire().await;
+            self.submit_op::<Op>(data, permit).await
+        })
+        .await
+    }
+
+    /// Same as [`Client::execute()`], but fails fast with [`Overloaded`] if the maximum number of
+    /// requests is already in flight.
+    pub async fn try_execute<Op>(
+        &self,
+        input: impl Into<Op::Input>,
+    ) -> Result<Result<Op::Output, Op::Error>, Overloaded>
+    where
+        Op: Operation + 'static,
+    {
+        let input = input.into();
+        traced(Op::CODE, async move {
+            let data = match Op::encode(input) {
+                ControlFlow::Continue(data) => data,
+                ControlFlow::Break(output) => return Ok(Ok(output)),
+            };
+            let permit = self.limit.try_acquire()?;
+            Ok(self.submit_op::<Op>(data, permit).await)
+        })
+        .await
+    }
+
+    /// Submits the provided raw `payload` with the provided `operation`, returning the raw reply
+    /// payload along with the cluster timestamp of the reply.
+    ///
+    /// Allows executing operations not covered by this crate yet, without implementing an
+    /// [`Operation`] for them. Unlike the typed methods, an empty `payload` is submitted as is.
+    pub async fn submit_raw<T>(
+        &self,
+        operation: core::Operation,
+        payload: T,
+    ) -> Result<(Vec<u8>, SystemTime), SendError>
+    where
+        T: Into<SendOwnedSlice<u8>>,
+    {
+        let payload = payload.into();
+        traced(operation, async move {
+            let permit = self.acquire().await;
+            self.request(payload.into_as_bytes(), operation, permit, |reply| {
+                reply.map(|r| (r.payload.to_vec(), r.timestamp))
+            })
+            .await
+        })
+        .await
+    }
+
     pub async fn create_accounts<T>(&self, accounts: T) -> Result<(), CreateAccountsError>
     where
         T: Into<SendOwnedSlice<Account>>,
     {
-        let accounts: SendOwnedSlice<Account> = accounts.into();
-        if accounts.is_empty() {
-            return Ok(());
-        }
-        Ok(self
-            .submit(
-                accounts.into_as_bytes(),
-                core::OperationKind::CreateAccounts,
-            )
-            .await?
-            .into_create_accounts()?)
+        self.execute::<operation::CreateAccounts>(accounts).await
     }
 
     pub async fn create_transfers<T>(&self, transfers: T) -> Result<(), CreateTransfersError>
     where
         T: Into<SendOwnedSlice<Transfer>>,
     {
-        let transfers: SendOwnedSlice<Transfer> = transfers.into();
-        if transfers.is_empty() {
-            return Ok(());
-        }
-        Ok(self
-            .submit(
-                transfers.into_as_bytes(),
-                core::OperationKind::CreateTransfers,
-            )
-            .await?
-            .into_create_transfers()?)
+        self.execute::<operation::CreateTransfers>(transfers).await
     }
 
     pub async fn get_account_balances<T>(
@@ -82,103 +236,430 @@ impl Client {
     where
         T: RawConstPtr<Target = account::Filter> + Send + 'static,
     {
-        let filter: SendOwnedSlice<account::Filter> = SendOwnedSlice::from_single(filter);
-        self.submit(
-            filter.into_as_bytes(),
-            core::OperationKind::GetAccountBalances,
+        self.execute::<operation::GetAccountBalances>(SendOwnedSlice::from_single(filter))
+            .await
+    }
+
+    /// Same as [`Client::get_account_balances()`], but decodes the reply into the provided
+    /// `balances` buffer (clearing it first) instead of allocating a new one.
+    ///
+    /// On error, the `balances` buffer is left empty, but keeps its capacity. If the returned
+    /// [`Future`] is dropped before completion, the buffer is left empty without capacity, as its
+    /// allocation is owned by the submitted request until the reply is received.
+    pub async fn get_account_balances_into<T>(
+        &self,
+        filter: T,
+        balances: &mut Vec<account::Balance>,
+    ) -> Result<(), SendError>
+    where
+        T: RawConstPtr<Target = account::Filter> + Send + 'static,
+    {
+        self.execute_into::<operation::GetAccountBalances, _>(
+            SendOwnedSlice::from_single(filter),
+            balances,
         )
         .await
-        .map(Reply::into_get_account_balances)
     }
 
     pub async fn get_account_transfers<T>(&self, filter: T) -> Result<Vec<Transfer>, SendError>
     where
         T: RawConstPtr<Target = account::Filter> + Send + 'static,
     {
-        let filter: SendOwnedSlice<account::Filter> = SendOwnedSlice::from_single(filter);
-        self.submit(
-            filter.into_as_bytes(),
-            core::OperationKind::GetAccountTransfers,
+        self.execute::<operation::GetAccountTransfers>(SendOwnedSlice::from_single(filter))
+            .await
+    }
+
+    /// Same as [`Client::get_account_transfers()`], but decodes the reply into the provided
+    /// `transfers` buffer (clearing it first) instead of allocating a new one.
+    ///
+    /// On error, the `transfers` buffer is left empty, but keeps its capacity. If the returned
+    /// [`Future`] is dropped before completion, the buffer is left empty without capacity, as its
+    /// allocation is owned by the submitted request until the reply is received.
+    pub async fn get_account_transfers_into<T>(
+        &self,
+        filter: T,
+        transfers: &mut Vec<Transfer>,
+    ) -> Result<(), SendError>
+    where
+        T: RawConstPtr<Target = account::Filter> + Send + 'static,
+    {
+        self.execute_into::<operation::GetAccountTransfers, _>(
+            SendOwnedSlice::from_single(filter),
+            transfers,
         )
         .await
-        .map(Reply::into_get_account_transfers)
     }
 
     pub async fn lookup_accounts<T>(&self, ids: T) -> Result<Vec<Account>, SendError>
     where
         T: Into<SendOwnedSlice<u128>>,
     {
-        let ids: SendOwnedSlice<u128> = ids.into();
-        if ids.is_empty() {
-            return Ok(Vec::new());
-        }
-        self.submit(ids.into_as_bytes(), core::OperationKind::LookupAccounts)
+        self.execute::<operation::LookupAccounts>(ids).await
+    }
+
+    /// Same as [`Client::lookup_accounts()`], but decodes the reply into the provided `accounts`
+    /// buffer (clearing it first) instead of allocating a new one.
+    ///
+    /// On error, the `accounts` buffer is left empty, but keeps its capacity. If the returned
+    /// [`Future`] is dropped before completion, the buffer is left empty without capacity, as its
+    /// allocation is owned by the submitted request until the reply is received.
+    pub async fn lookup_accounts_into<T>(
+        &self,
+        ids: T,
+        accounts: &mut Vec<Account>,
+    ) -> Result<(), SendError>
+    where
+        T: Into<SendOwnedSlice<u128>>,
+    {
+        self.execute_into::<operation::LookupAccounts, _>(ids.into(), accounts)
             .await
-            .map(Reply::into_lookup_accounts)
     }
 
     pub async fn lookup_transfers<T>(&self, ids: T) -> Result<Vec<Transfer>, SendError>
     where
         T: Into<SendOwnedSlice<u128>>,
     {
-        let ids: SendOwnedSlice<u128> = ids.into();
-        if ids.is_empty() {
-            return Ok(Vec::new());
-        }
-        self.submit(ids.into_as_bytes(), core::OperationKind::LookupTransfers)
+        self.execute::<operation::LookupTransfers>(ids).await
+    }
+
+    /// Same as [`Client::lookup_transfers()`], but decodes the reply into the provided `transfers`
+    /// buffer (clearing it first) instead of allocating a new one.
+    ///
+    /// On error, the `transfers` buffer is left empty, but keeps its capacity. If the returned
+    /// [`Future`] is dropped before completion, the buffer is left empty without capacity, as its
+    /// allocation is owned by the submitted request until the reply is received.
+    pub async fn lookup_transfers_into<T>(
+        &self,
+        ids: T,
+        transfers: &mut Vec<Transfer>,
+    ) -> Result<(), SendError>
+    where
+        T: Into<SendOwnedSlice<u128>>,
+    {
+        self.execute_into::<operation::LookupTransfers, _>(ids.into(), transfers)
             .await
-            .map(Reply::into_lookup_transfers)
     }
 
     pub async fn query_accounts<T>(&self, filter: T) -> Result<Vec<Account>, SendError>
     where
         T: RawConstPtr<Target = QueryFilter> + Send + 'static,
     {
-        let filter: SendOwnedSlice<QueryFilter> = SendOwnedSlice::from_single(filter);
-        self.submit(filter.into_as_bytes(), core::OperationKind::QueryAccounts)
+        self.execute::<operation::QueryAccounts>(SendOwnedSlice::from_single(filter))
             .await
-            .map(Reply::into_query_accounts)
+    }
+
+    /// Same as [`Client::query_accounts()`], but decodes the reply into the provided `accounts`
+    /// buffer (clearing it first) instead of allocating a new one.
+    ///
+    /// On error, the `accounts` buffer is left empty, but keeps its capacity. If the returned
+    /// [`Future`] is dropped before completion, the buffer is left empty without capacity, as its
+    /// allocation is owned by the submitted request until the reply is received.
+    pub async fn query_accounts_into<T>(
+        &self,
+        filter: T,
+        accounts: &mut Vec<Account>,
+    ) -> Result<(), SendError>
+    where
+        T: RawConstPtr<Target = QueryFilter> + Send + 'static,
+    {
+        self.execute_into::<operation::QueryAccounts, _>(
+            SendOwnedSlice::from_single(filter),
+            accounts,
+        )
+        .await
     }
 
     pub async fn query_transfers<T>(&self, filter: T) -> Result<Vec<Transfer>, SendError>
     where
         T: RawConstPtr<Target = QueryFilter> + Send + 'static,
     {
-        let filter: SendOwnedSlice<QueryFilter> = SendOwnedSlice::from_single(filter);
-        self.submit(filter.into_as_bytes(), core::OperationKind::QueryTransfers)
+        self.execute::<operation::QueryTransfers>(SendOwnedSlice::from_single(filter))
+            .await
+    }
+
+    /// Same as [`Client::query_transfers()`], but decodes the reply into the provided `transfers`
+    /// buffer (clearing it first) instead of allocating a new one.
+    ///
+    /// On error, the `transfers` buffer is left empty, but keeps its capacity. If the returned
+    /// [`Future`] is dropped before completion, the buffer is left empty without capacity, as its
+    /// allocation is owned by the submitted request until the reply is received.
+    pub async fn query_transfers_into<T>(
+        &self,
+        filter: T,
+        transfers: &mut Vec<Transfer>,
+    ) -> Result<(), SendError>
+    where
+        T: RawConstPtr<Target = QueryFilter> + Send + 'static,
+    {
+        self.execute_into::<operation::QueryTransfers, _>(
+            SendOwnedSlice::from_single(filter),
+            transfers,
+        )
+        .await
+    }
+
+    /// Same as [`Client::create_accounts()`], but fails fast with [`Overloaded`] if the maximum
+    /// number of requests is already in flight.
+    pub async fn try_create_accounts<T>(
+        &self,
+        accounts: T,
+    ) -> Result<Result<(), CreateAccountsError>, Overloaded>
+    where
+        T: Into<SendOwnedSlice<Account>>,
+    {
+        self.try_execute::<operation::CreateAccounts>(accounts)
+            .await
+    }
+
+    /// Same as [`Client::create_transfers()`], but fails fast with [`Overloaded`] if the maximum
+    /// number of requests is already in flight.
+    pub async fn try_create_transfers<T>(
+        &self,
+        transfers: T,
+    ) -> Result<Result<(), CreateTransfersError>, Overloaded>
+    where
+        T: Into<SendOwnedSlice<Transfer>>,
+    {
+        self.try_execute::<operation::CreateTransfers>(transfers)
+            .await
+    }
+
+    /// Same as [`Client::get_account_balances()`], but fails fast with [`Overloaded`] if the
+    /// maximum number of requests is already in flight.
+    pub async fn try_get_account_balances<T>(
+        &self,
+        filter: T,
+    ) -> Result<Result<Vec<account::Balance>, SendError>, Overloaded>
+    where
+        T: RawConstPtr<Target = account::Filter> + Send + 'static,
+    {
+        self.try_execute::<operation::GetAccountBalances>(SendOwnedSlice::from_single(filter))
+            .await
+    }
+
+    /// Same as [`Client::get_account_transfers()`], but fails fast with [`Overloaded`] if the
+    /// maximum number of requests is already in flight.
+    pub async fn try_get_account_transfers<T>(
+        &self,
+        filter: T,
+    ) -> Result<Result<Vec<Transfer>, SendError>, Overloaded>
+    where
+        T: RawConstPtr<Target = account::Filter> + Send + 'static,
+    {
+        self.try_execute::<operation::GetAccountTransfers>(SendOwnedSlice::from_single(filter))
+            .await
+    }
+
+    /// Same as [`Client::lookup_accounts()`], but fails fast with [`Overloaded`] if the maximum
+    /// number of requests is already in flight.
+    pub async fn try_lookup_accounts<T>(
+        &self,
+        ids: T,
+    ) -> Result<Result<Vec<Account>, SendError>, Overloaded>
+    where
+        T: Into<SendOwnedSlice<u128>>,
+    {
+        self.try_execute::<operation::LookupAccounts>(ids).await
+    }
+
+    /// Same as [`Client::lookup_transfers()`], but fails fast with [`Overloaded`] if the maximum
+    /// number of requests is already in flight.
+    pub async fn try_lookup_transfers<T>(
+        &self,
+        ids: T,
+    ) -> Result<Result<Vec<Transfer>, SendError>, Overloaded>
+    where
+        T: Into<SendOwnedSlice<u128>>,
+    {
+        self.try_execute::<operation::LookupTransfers>(ids).await
+    }
+
+    /// Same as [`Client::query_accounts()`], but fails fast with [`Overloaded`] if the maximum
+    /// number of requests is already in flight.
+    pub async fn try_query_accounts<T>(
+        &self,
+        filter: T,
+    ) -> Result<Result<Vec<Account>, SendError>, Overloaded>
+    where
+        T: RawConstPtr<Target = QueryFilter> + Send + 'static,
+    {
+        self.try_execute::<operation::QueryAccounts>(SendOwnedSlice::from_single(filter))
+            .await
+    }
+
+    /// Same as [`Client::query_transfers()`], but fails fast with [`Overloaded`] if the maximum
+    /// number of requests is already in flight.
+    pub async fn try_query_transfers<T>(
+        &self,
+        filter: T,
+    ) -> Result<Result<Vec<Transfer>, SendError>, Overloaded>
+    where
+        T: RawConstPtr<Target = QueryFilter> + Send + 'static,
+    {
+        self.try_execute::<operation::QueryTransfers>(SendOwnedSlice::from_single(filter))
             .await
-            .map(Reply::into_query_transfers)
     }
 
-    async fn submit(
+    /// Executes the provided [`Operation`], decoding its reply payload (being an array of `T`)
+    /// right into the provided `buf`.
+    async fn execute_into<Op, T>(&self, input: Op::Input, buf: &mut Vec<T>) -> Result<(), Op::Error>
+    where
+        Op: Operation<Output = Vec<T>>,
+        T: Pod + Send,
+    {
+        traced(Op::CODE, async move {
+            let data = match Op::encode(input) {
+                ControlFlow::Continue(data) => data,
+                ControlFlow::Break(output) => {
+                    buf.clear();
+                    buf.extend(output);
+                    return Ok(());
+                }
+            };
+            let permit = self.acquire().await;
+            let buffer = mem::take(buf);
+            // The `buffer` is returned on error too, so its allocation is reused next time.
+            let (buffer, result) = self
+                .request(data, Op::CODE, permit, move |reply| match reply {
+                    Ok(r) => (operation::decode_into(buffer, r.payload), Ok(())),
+                    Err(e) => (operation::decode_into(buffer, &[]), Err(e)),
+                })
+                .await;
+            *buf = buffer;
+            result.map_err(Into::into)
+        })
+        .await
+    }
+
+    /// Submits the already encoded `data` of the provided [`Operation`], releasing the `permit`
+    /// once its reply is received.
+    async fn submit_op<Op>(
+        &self,
+        data: SendAsBytesOwnedSlice,
+        permit: Permit,
+    ) -> Result<Op::Output, Op::Error>
+    where
+        Op: Operation + 'static,
+    {
+        self.request(data, Op::CODE, permit, |reply| {
+            reply.map_err(Into::into).and_then(Op::decode)
+        })
+        .await
+    }
+
+    /// Waits for the admission of a new request, according to the limit of requests in flight.
+    async fn acquire(&self) -> Permit {
+        #[cfg(feature = "tracing")]
+        let started_at = Instant::now();
+        let permit = self.limit.acquire().await;
+        #[cfg(feature = "tracing")]
+        trace::record_admission(started_at.elapsed());
+        permit
+    }
+
+    /// Submits the provided `data`, releasing the `permit` once its reply is received, and
+    /// returning the reply processed by the provided `handle` function.
+    async fn request<T>(
         &self,
         data: SendAsBytesOwnedSlice,
-        operation: impl Into<core::Operation>,
-    ) -> Result<Reply, SendError> {
+        operation: core::Operation,
+        permit: Permit,
+        handle: impl FnOnce(Result<core::Reply<'_>, SendError>) -> T + Send + 'static,
+    ) -> T
+    where
+        T: Send + 'static,
+    {
         let (reply_sender, reply_receiver) = oneshot::channel();
-        let user_data = Box::new(UserData { reply_sender, data });
-        self.inner.submit(Packet::new(user_data, operation));
-        reply_receiver.await.unwrap()
+        self.submit(data, operation, move |reply| {
+            drop(permit);
+            // Channel may be closed due to the `Future` cancellation, so the `.send()` error
+            // should be ignored.
+            reply_sender
+                .send((handle(reply), Instant::now()))
+                .unwrap_or_else(drop);
+        });
+        let (output, received_at) = reply_receiver.await.unwrap();
+        #[cfg(feature = "tracing")]
+        trace::record_resume(received_at);
+        #[cfg(not(feature = "tracing"))]
+        let _ = received_at;
+        output
+    }
+
+    /// Submits the provided `data`, calling `complete` once the reply is received, and tracking
+    /// the requests in flight and whether this [`Client`] is evicted.
+    fn submit<F>(&self, data: SendAsBytesOwnedSlice, operation: core::Operation, complete: F)
+    where
+        F: FnOnce(Result<core::Reply<'_>, SendError>) + Send + 'static,
+    {
+        let eviction = Arc::clone(&self.eviction);
+        let in_flight = Arc::clone(&self.in_flight);
+        in_flight.fetch_add(1, Ordering::Relaxed);
+        submit(&self.inner, data, operation, move |reply| {
+            in_flight.fetch_sub(1, Ordering::Relaxed);
+            if let Some(e) = reply.as_ref().err().copied().filter(|e| is_eviction(*e)) {
+                // Only the first eviction matters, as all the further requests fail anyway.
+                _ = eviction.set(e);
+            }
+            complete(reply);
+        });
     }
 }
 
+/// Instruments the provided `future` of a request of the provided `operation` with a
+/// [`tracing::Span`], if the `tracing` feature is enabled.
+fn traced<F>(operation: core::Operation, future: F) -> impl Future<Output = F::Output>
+where
+    F: Future,
+{
+    #[cfg(feature = "tracing")]
+    let future = tracing::Instrument::instrument(future, trace::span(operation));
+    #[cfg(not(feature = "tracing"))]
+    let _ = operation;
+    future
+}
+
+/// Checks whether the provided [`SendError`] indicates the client being evicted by the cluster.
+fn is_eviction(e: SendError) -> bool {
+    matches!(
+        e.kind(),
+        SendErrorKind::ClientEvicted
+            | SendErrorKind::ClientReleaseTooLow
+            | SendErrorKind::ClientReleaseTooHigh,
+    )
+}
+
+/// Submits the provided `data` via the `client`, calling `complete` once the reply is received.
+fn submit<F>(
+    client: &core::Client<&'static Callbacks>,
+    data: SendAsBytesOwnedSlice,
+    operation: core::Operation,
+    complete: F,
+) where
+    F: FnOnce(Result<core::Reply<'_>, SendError>) + Send + 'static,
+{
+    #[cfg(feature = "metrics")]
+    let complete = telemetry::record(operation, &data, complete);
+    #[cfg(feature = "tracing")]
+    let complete = trace::instrument(operation, &data, complete);
+    let user_data = Box::new(UserData {
+        complete: Box::new(complete),
+        data,
+    });
+    client.submit(Packet::new(user_data, operation));
+}
+
 impl core::Callbacks for Callbacks {
     type UserDataPtr = Box<UserData>;
 
     fn completion(&self, packet: Packet<Self::UserDataPtr>, reply: Option<core::Reply<'_>>) {
         let status = packet.status();
-        let operation = packet.operation();
-        let user_data = packet.into_user_data();
-        // Channel may be closed due to the `Future` cancellation, so the `.send()` error should
-        // be ignored.
-        user_data
-            .reply_sender
-            .send(status.map(|()| {
-                // PANIC: Unwrapping is OK here, because the `reply` can only be `None` when the
-                //        `status` is `Err`.
-                Reply::copy_from_reply(operation.kind(), reply.unwrap().payload)
-            }))
-            .unwrap_or_else(drop);
+        let UserData { complete, .. } = *packet.into_user_data();
+        complete(status.map(|()| {
+            // PANIC: Unwrapping is OK here, because the `reply` can only be `None` when the
+            //        `status` is `Err`.
+            reply.unwrap()
+        }));
     }
 }
 
@@ -190,6 +671,7 @@ impl core::UserData for UserData {
 
 fn _test_thread_safe(
     client: Client,
+    pool: ClientPool,
     accounts: Vec<Account>,
     transfers: Vec<Transfer>,
     query_filter: &'static QueryFilter,
@@ -197,14 +679,63 @@ fn _test_thread_safe(
     ids: Vec<u128>,
 ) {
     check_thread_safe(async move {
+        pool.lookup_accounts(ids.clone()).await.unwrap();
+        pool.execute::<operation::LookupTransfers>(ids.clone())
+            .await
+            .unwrap();
         client.create_accounts(accounts).await.unwrap();
         client.create_transfers(transfers).await.unwrap();
         client.get_account_balances(account_filter).await.unwrap();
         client.get_account_transfers(account_filter).await.unwrap();
         client.lookup_accounts(ids.clone()).await.unwrap();
-        client.lookup_transfers(ids).await.unwrap();
+        client.lookup_transfers(ids.clone()).await.unwrap();
         client.query_accounts(query_filter).await.unwrap();
         client.query_transfers(query_filter).await.unwrap();
+        client
+            .execute::<operation::LookupAccounts>(ids.clone())
+            .await
+            .unwrap();
+        client
+            .try_lookup_accounts(ids.clone())
+            .await
+            .unwrap()
+            .unwrap();
+        client
+            .try_execute::<operation::LookupTransfers>(ids.clone())
+            .await
+            .unwrap()
+            .unwrap();
+        client
+            .submit_raw(core::Operation::from_code(255), vec![0_u8; 16])
+            .await
+            .unwrap();
+        client.check_release().await.unwrap();
+
+        let (mut accounts, mut balances, mut transfers) = (Vec::new(), Vec::new(), Vec::new());
+        client
+            .get_account_balances_into(account_filter, &mut balances)
+            .await
+            .unwrap();
+        client
+            .get_account_transfers_into(account_filter, &mut transfers)
+            .await
+            .unwrap();
+        client
+            .lookup_accounts_into(ids.clone(), &mut accounts)
+            .await
+            .unwrap();
+        client
+            .lookup_transfers_into(ids, &mut transfers)
+            .await
+            .unwrap();
+        client
+            .query_accounts_into(query_filter, &mut accounts)
+            .await
+            .unwrap();
+        client
+            .query_transfers_into(query_filter, &mut transfers)
+            .await
+            .unwrap();
     });
 
     fn check_thread_safe<T>(_: T)
@@ -213,3 +744,38 @@ fn _test_thread_safe(
     {
     }
 }
+
+#[cfg(test)]
+mod client_spec {
+    use std::{
+        future::Future as _,
+        pin::pin,
+        sync::Arc,
+        task::{Context, Wake, Waker},
+    };
+
+    use super::Client;
+
+    struct NoopWake;
+
+    impl Wake for NoopWake {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    #[test]
+    #[ignore = "starts a `tb_client` session"]
+    fn check_release_submits_request() {
+        let client = Client::new(0, "3000").unwrap();
+        let waker = Waker::from(Arc::new(NoopWake));
+
+        {
+            let mut check = pin!(client.check_release());
+
+            assert!(check
+                .as_mut()
+                .poll(&mut Context::from_waker(&waker))
+                .is_pending());
+            assert_eq!(client.in_flight(), 1);
+        }
+    }
+}
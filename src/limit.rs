@@ -0,0 +1,93 @@
+//! Admission limit of requests in flight.
+
+use std::{error::Error, fmt, num::NonZeroUsize, sync::Arc};
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Error returned by `try_` methods of a [`Client`], indicating that it already has the maximum
+/// number of requests in flight.
+///
+/// The request is not submitted.
+///
+/// [`Client`]: crate::Client
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Overloaded(());
+
+/// Limit of requests in flight, if any.
+#[derive(Default)]
+pub(crate) struct Limit(Option<Arc<Semaphore>>);
+
+/// Permission for a request to be in flight, released once dropped.
+pub(crate) type Permit = Option<OwnedSemaphorePermit>;
+
+impl Limit {
+    /// Creates a new [`Limit`] of the provided `max_in_flight` requests.
+    ///
+    /// Values above [`Semaphore::MAX_PERMITS`] are clamped to it, being practically unlimited
+    /// anyway.
+    pub(crate) fn new(max_in_flight: NonZeroUsize) -> Self {
+        let permits = max_in_flight.get().min(Semaphore::MAX_PERMITS);
+        Self(Some(Arc::new(Semaphore::new(permits))))
+    }
+
+    /// Waits until the request is allowed to be in flight.
+    pub(crate) async fn acquire(&self) -> Permit {
+        let semaphore = Arc::clone(self.0.as_ref()?);
+        // PANIC: Unwrapping is OK here, because the `Semaphore` is never closed.
+        Some(semaphore.acquire_owned().await.unwrap())
+    }
+
+    /// Allows the request to be in flight, unless the limit is already reached.
+    pub(crate) fn try_acquire(&self) -> Result<Permit, Overloaded> {
+        let Some(semaphore) = &self.0 else {
+            return Ok(None);
+        };
+        Arc::clone(semaphore)
+            .try_acquire_owned()
+            .map(Some)
+            .map_err(|_| Overloaded(()))
+    }
+}
+
+impl fmt::Display for Overloaded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        "Too many requests in flight".fmt(f)
+    }
+}
+
+impl Error for Overloaded {}
+
+#[cfg(test)]
+mod limit_spec {
+    use std::num::NonZeroUsize;
+
+    use super::{Limit, Overloaded};
+
+    #[test]
+    fn unlimited_never_overloads() {
+        let limit = Limit::default();
+
+        let permits = (0..3).map(|_| limit.try_acquire()).collect::<Vec<_>>();
+
+        assert!(permits.iter().all(|p| matches!(p, Ok(None))));
+    }
+
+    #[test]
+    fn overloads_until_permit_is_released() {
+        let limit = Limit::new(NonZeroUsize::MIN);
+
+        let permit = pollster::block_on(limit.acquire());
+        assert!(permit.is_some());
+        assert_eq!(limit.try_acquire().err(), Some(Overloaded(())));
+
+        drop(permit);
+        assert!(matches!(limit.try_acquire(), Ok(Some(_))));
+    }
+
+    #[test]
+    fn clamps_too_high_limit() {
+        let limit = Limit::new(NonZeroUsize::MAX);
+
+        assert!(matches!(limit.try_acquire(), Ok(Some(_))));
+    }
+}
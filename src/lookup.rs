@@ -0,0 +1,174 @@
+//! Lookups returning maps of found records along with the missing IDs.
+
+use std::{collections::HashMap, error::Error, fmt};
+
+use core::{error::SendError, util::SendOwnedSlice};
+
+use crate::{operation, Account, Client, Operation, Transfer};
+
+/// Maximum number of IDs looked up in a single request.
+///
+/// Limited by the number of [`Account`]s (being the larger records) fitting into a single reply
+/// message of the default 1 MiB size, without its 256 bytes header.
+pub const LOOKUP_CHUNK_SIZE: usize = operation::MESSAGE_CAPACITY;
+
+/// Result of looking up records by their IDs.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct Lookup<T> {
+    /// Found records by their IDs.
+    pub found: HashMap<u128, T>,
+
+    /// IDs of the records not found, in the order they were requested.
+    pub missing: Vec<u128>,
+}
+
+/// Error of strict lookups, failing on any missing ID.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum LookupError {
+    Send(SendError),
+    /// IDs of the records not found, in the order they were requested.
+    Missing(Vec<u128>),
+}
+
+impl Client {
+    /// Looks up [`Account`]s with the provided `ids`, returning them by their IDs along with the
+    /// IDs not found.
+    ///
+    /// The `ids` are looked up in chunks of [`LOOKUP_CHUNK_SIZE`], so may exceed a single message.
+    pub async fn lookup_accounts_map<T>(&self, ids: T) -> Result<Lookup<Account>, SendError>
+    where
+        T: Into<SendOwnedSlice<u128>>,
+    {
+        self.lookup_map::<operation::LookupAccounts, _>(ids.into(), Account::id)
+            .await
+    }
+
+    /// Looks up [`Transfer`]s with the provided `ids`, returning them by their IDs along with the
+    /// IDs not found.
+    ///
+    /// The `ids` are looked up in chunks of [`LOOKUP_CHUNK_SIZE`], so may exceed a single message.
+    pub async fn lookup_transfers_map<T>(&self, ids: T) -> Result<Lookup<Transfer>, SendError>
+    where
+        T: Into<SendOwnedSlice<u128>>,
+    {
+        self.lookup_map::<operation::LookupTransfers, _>(ids.into(), Transfer::id)
+            .await
+    }
+
+    /// Same as [`Client::lookup_accounts_map()`], but fails with [`LookupError::Missing`] if any
+    /// of the `ids` is not found.
+    pub async fn lookup_accounts_strict<T>(
+        &self,
+        ids: T,
+    ) -> Result<HashMap<u128, Account>, LookupError>
+    where
+        T: Into<SendOwnedSlice<u128>>,
+    {
+        self.lookup_accounts_map(ids).await?.into_strict()
+    }
+
+    /// Same as [`Client::lookup_transfers_map()`], but fails with [`LookupError::Missing`] if any
+    /// of the `ids` is not found.
+    pub async fn lookup_transfers_strict<T>(
+        &self,
+        ids: T,
+    ) -> Result<HashMap<u128, Transfer>, LookupError>
+    where
+        T: Into<SendOwnedSlice<u128>>,
+    {
+        self.lookup_transfers_map(ids).await?.into_strict()
+    }
+
+    async fn lookup_map<Op, T>(
+        &self,
+        ids: SendOwnedSlice<u128>,
+        id: fn(&T) -> u128,
+    ) -> Result<Lookup<T>, SendError>
+    where
+        Op: Operation<Input = SendOwnedSlice<u128>, Output = Vec<T>, Error = SendError> + 'static,
+    {
+        let mut found = HashMap::with_capacity(ids.len());
+        if ids.len() <= LOOKUP_CHUNK_SIZE {
+            let requested = ids.as_slice().to_vec();
+            found.extend(
+                self.execute::<Op>(ids)
+                    .await?
+                    .into_iter()
+                    .map(|r| (id(&r), r)),
+            );
+            return Ok(Lookup::new(&requested, found));
+        }
+        for chunk in ids.as_slice().chunks(LOOKUP_CHUNK_SIZE) {
+            let records = self.execute::<Op>(chunk.to_vec()).await?;
+            found.extend(records.into_iter().map(|r| (id(&r), r)));
+        }
+        Ok(Lookup::new(ids.as_slice(), found))
+    }
+}
+
+impl<T> Lookup<T> {
+    fn new(requested: &[u128], found: HashMap<u128, T>) -> Self {
+        let missing = requested
+            .iter()
+            .copied()
+            .filter(|id| !found.contains_key(id))
+            .collect();
+        Self { found, missing }
+    }
+
+    /// Returns the found records, or fails with [`LookupError::Missing`] if any is missing.
+    pub fn into_strict(self) -> Result<HashMap<u128, T>, LookupError> {
+        if self.missing.is_empty() {
+            Ok(self.found)
+        } else {
+            Err(LookupError::Missing(self.missing))
+        }
+    }
+}
+
+impl Error for LookupError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Send(e) => Some(e),
+            Self::Missing(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for LookupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Failed to look up: ")?;
+        match self {
+            Self::Send(e) => write!(f, "{e}"),
+            Self::Missing(ids) => write!(f, "{} of the IDs are not found", ids.len()),
+        }
+    }
+}
+
+impl From<SendError> for LookupError {
+    fn from(value: SendError) -> Self {
+        Self::Send(value)
+    }
+}
+
+#[cfg(test)]
+mod lookup_spec {
+    use std::collections::HashMap;
+
+    use super::{Lookup, LookupError};
+
+    #[test]
+    fn reports_missing_in_requested_order() {
+        let found = HashMap::from([(2, "b")]);
+
+        let lookup = Lookup::new(&[3, 2, 1], found);
+
+        assert_eq!(lookup.missing, [3, 1]);
+        assert!(matches!(
+            lookup.into_strict(),
+            Err(LookupError::Missing(ids)) if ids == [3, 1],
+        ));
+    }
+}
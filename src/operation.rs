@@ -0,0 +1,304 @@
+//! Typed [TigerBeetle] operations, [executed] by a [`Client`].
+//!
+//! [`Client`]: crate::Client
+//! [executed]: crate::Client::execute
+//! [TigerBeetle]: https://tigerbeetle.com
+
+use std::{mem, ops::ControlFlow};
+
+use bytemuck::Pod;
+#[cfg(any(feature = "metrics", feature = "tracing"))]
+use core::error::{
+    CreateAccountErrorKind, CreateAccountsIndividualApiError, CreateTransferErrorKind,
+    CreateTransfersIndividualApiError,
+};
+use core::{
+    error::{
+        CreateAccountsApiError, CreateAccountsError, CreateTransfersApiError, CreateTransfersError,
+        SendError,
+    },
+    util::{SendAsBytesOwnedSlice, SendOwnedSlice},
+    OperationKind,
+};
+
+use crate::{account, Account, QueryFilter, Transfer};
+
+/// Maximum number of records (all of [`Account`]s, [`Transfer`]s and [`account::Balance`]s are
+/// 128 bytes) fitting into a single message of the default 1 MiB size, without its 256 bytes
+/// header.
+pub(crate) const MESSAGE_CAPACITY: usize = (1024 * 1024 - 256) / mem::size_of::<Account>();
+
+const _: () = assert!(
+    mem::size_of::<Transfer>() == mem::size_of::<Account>()
+        && mem::size_of::<account::Balance>() == mem::size_of::<Account>()
+);
+
+/// Operation to be [executed] by a [`Client`].
+///
+/// Implement it to execute operations not covered by this crate yet.
+///
+/// [`Client`]: crate::Client
+/// [executed]: crate::Client::execute
+pub trait Operation {
+    /// Code of this [`Operation`] to submit.
+    const CODE: core::Operation;
+
+    /// Input provided for executing this [`Operation`].
+    type Input;
+
+    /// Output of successfully executed [`Operation`].
+    type Output: Send + 'static;
+
+    /// Error of executing this [`Operation`].
+    type Error: From<SendError> + Send + 'static;
+
+    /// Encodes the provided `input` into the payload to submit.
+    ///
+    /// Returns [`ControlFlow::Break`] with the output, if there is nothing to submit.
+    fn encode(input: Self::Input) -> ControlFlow<Self::Output, SendAsBytesOwnedSlice>;
+
+    /// Decodes the received `reply` into the output.
+    ///
+    /// Called on the client's internal thread, so should return fast.
+    fn decode(reply: core::Reply<'_>) -> Result<Self::Output, Self::Error>;
+}
+
+/// [`Operation`] creating [`Account`]s.
+pub enum CreateAccounts {}
+
+/// [`Operation`] creating [`Transfer`]s.
+pub enum CreateTransfers {}
+
+/// [`Operation`] getting historical [`account::Balance`]s of an [`Account`].
+pub enum GetAccountBalances {}
+
+/// [`Operation`] getting [`Transfer`]s of an [`Account`].
+pub enum GetAccountTransfers {}
+
+/// [`Operation`] looking up [`Account`]s by their IDs.
+pub enum LookupAccounts {}
+
+/// [`Operation`] looking up [`Transfer`]s by their IDs.
+pub enum LookupTransfers {}
+
+/// [`Operation`] querying [`Account`]s by a [`QueryFilter`].
+pub enum QueryAccounts {}
+
+/// [`Operation`] querying [`Transfer`]s by a [`QueryFilter`].
+pub enum QueryTransfers {}
+
+impl Operation for CreateAccounts {
+    const CODE: core::Operation = core::Operation::from_code(OperationKind::CreateAccounts as u8);
+
+    type Input = SendOwnedSlice<Account>;
+    type Output = ();
+    type Error = CreateAccountsError;
+
+    fn encode(accounts: Self::Input) -> ControlFlow<Self::Output, SendAsBytesOwnedSlice> {
+        if accounts.is_empty() {
+            return ControlFlow::Break(());
+        }
+        ControlFlow::Continue(accounts.into_as_bytes())
+    }
+
+    fn decode(reply: core::Reply<'_>) -> Result<Self::Output, Self::Error> {
+        let results = bytemuck::pod_collect_to_vec(reply.payload);
+        CreateAccountsApiError::from_raw_results(results).map_or(Ok(()), |e| Err(e.into()))
+    }
+}
+
+impl Operation for CreateTransfers {
+    const CODE: core::Operation = core::Operation::from_code(OperationKind::CreateTransfers as u8);
+
+    type Input = SendOwnedSlice<Transfer>;
+    type Output = ();
+    type Error = CreateTransfersError;
+
+    fn encode(transfers: Self::Input) -> ControlFlow<Self::Output, SendAsBytesOwnedSlice> {
+        if transfers.is_empty() {
+            return ControlFlow::Break(());
+        }
+        ControlFlow::Continue(transfers.into_as_bytes())
+    }
+
+    fn decode(reply: core::Reply<'_>) -> Result<Self::Output, Self::Error> {
+        let results = bytemuck::pod_collect_to_vec(reply.payload);
+        CreateTransfersApiError::from_raw_results(results).map_or(Ok(()), |e| Err(e.into()))
+    }
+}
+
+impl Operation for GetAccountBalances {
+    const CODE: core::Operation =
+        core::Operation::from_code(OperationKind::GetAccountBalances as u8);
+
+    type Input = SendOwnedSlice<account::Filter>;
+    type Output = Vec<account::Balance>;
+    type Error = SendError;
+
+    fn encode(filter: Self::Input) -> ControlFlow<Self::Output, SendAsBytesOwnedSlice> {
+        ControlFlow::Continue(filter.into_as_bytes())
+    }
+
+    fn decode(reply: core::Reply<'_>) -> Result<Self::Output, Self::Error> {
+        Ok(decode_into(Vec::new(), reply.payload))
+    }
+}
+
+impl Operation for GetAccountTransfers {
+    const CODE: core::Operation =
+        core::Operation::from_code(OperationKind::GetAccountTransfers as u8);
+
+    type Input = SendOwnedSlice<account::Filter>;
+    type Output = Vec<Transfer>;
+    type Error = SendError;
+
+    fn encode(filter: Self::Input) -> ControlFlow<Self::Output, SendAsBytesOwnedSlice> {
+        ControlFlow::Continue(filter.into_as_bytes())
+    }
+
+    fn decode(reply: core::Reply<'_>) -> Result<Self::Output, Self::Error> {
+        Ok(decode_into(Vec::new(), reply.payload))
+    }
+}
+
+impl Operation for LookupAccounts {
+    const CODE: core::Operation = core::Operation::from_code(OperationKind::LookupAccounts as u8);
+
+    type Input = SendOwnedSlice<u128>;
+    type Output = Vec<Account>;
+    type Error = SendError;
+
+    fn encode(ids: Self::Input) -> ControlFlow<Self::Output, SendAsBytesOwnedSlice> {
+        if ids.is_empty() {
+            return ControlFlow::Break(Vec::new());
+        }
+        ControlFlow::Continue(ids.into_as_bytes())
+    }
+
+    fn decode(reply: core::Reply<'_>) -> Result<Self::Output, Self::Error> {
+        Ok(decode_into(Vec::new(), reply.payload))
+    }
+}
+
+impl Operation for LookupTransfers {
+    const CODE: core::Operation = core::Operation::from_code(OperationKind::LookupTransfers as u8);
+
+    type Input = SendOwnedSlice<u128>;
+    type Output = Vec<Transfer>;
+    type Error = SendError;
+
+    fn encode(ids: Self::Input) -> ControlFlow<Self::Output, SendAsBytesOwnedSlice> {
+        if ids.is_empty() {
+            return ControlFlow::Break(Vec::new());
+        }
+        ControlFlow::Continue(ids.into_as_bytes())
+    }
+
+    fn decode(reply: core::Reply<'_>) -> Result<Self::Output, Self::Error> {
+        Ok(decode_into(Vec::new(), reply.payload))
+    }
+}
+
+impl Operation for QueryAccounts {
+    const CODE: core::Operation = core::Operation::from_code(OperationKind::QueryAccounts as u8);
+
+    type Input = SendOwnedSlice<QueryFilter>;
+    type Output = Vec<Account>;
+    type Error = SendError;
+
+    fn encode(filter: Self::Input) -> ControlFlow<Self::Output, SendAsBytesOwnedSlice> {
+        ControlFlow::Continue(filter.into_as_bytes())
+    }
+
+    fn decode(reply: core::Reply<'_>) -> Result<Self::Output, Self::Error> {
+        Ok(decode_into(Vec::new(), reply.payload))
+    }
+}
+
+impl Operation for QueryTransfers {
+    const CODE: core::Operation = core::Operation::from_code(OperationKind::QueryTransfers as u8);
+
+    type Input = SendOwnedSlice<QueryFilter>;
+    type Output = Vec<Transfer>;
+    type Error = SendError;
+
+    fn encode(filter: Self::Input) -> ControlFlow<Self::Output, SendAsBytesOwnedSlice> {
+        ControlFlow::Continue(filter.into_as_bytes())
+    }
+
+    fn decode(reply: core::Reply<'_>) -> Result<Self::Output, Self::Error> {
+        Ok(decode_into(Vec::new(), reply.payload))
+    }
+}
+
+/// Clears the provided `buf` and decodes the `payload` right into it.
+///
+/// The `payload` is not required to be aligned for `T`.
+pub(crate) fn decode_into<T: Pod>(mut buf: Vec<T>, payload: &[u8]) -> Vec<T> {
+    buf.clear();
+    buf.extend(
+        payload
+            .chunks_exact(mem::size_of::<T>())
+            .map(bytemuck::pod_read_unaligned::<T>),
+    );
+    buf
+}
+
+/// Individual error of an event, decoded from a reply by [`create_errors()`].
+#[cfg(any(feature = "metrics", feature = "tracing"))]
+#[cfg_attr(not(feature = "metrics"), allow(dead_code))] // kinds are only counted for `tracing`
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum CreateErrorKind {
+    Account(CreateAccountErrorKind),
+    Transfer(CreateTransferErrorKind),
+}
+
+/// Decodes individual errors of events from the reply `payload` of the provided `operation`.
+///
+/// Yields nothing for operations not reporting individual errors.
+#[cfg(any(feature = "metrics", feature = "tracing"))]
+pub(crate) fn create_errors(
+    operation: core::Operation,
+    payload: &[u8],
+) -> impl Iterator<Item = CreateErrorKind> + '_ {
+    fn decode<T: Pod>(payload: &[u8]) -> impl Iterator<Item = T> + '_ {
+        payload
+            .chunks_exact(mem::size_of::<T>())
+            .map(bytemuck::pod_read_unaligned::<T>)
+    }
+
+    let (accounts, transfers): (&[u8], &[u8]) = match operation.kind() {
+        OperationKind::CreateAccounts => (payload, &[]),
+        OperationKind::CreateTransfers => (&[], payload),
+        _ => (&[], &[]),
+    };
+    let accounts = decode(accounts)
+        .filter_map(CreateAccountsIndividualApiError::from_raw_result)
+        .map(|e| CreateErrorKind::Account(e.kind()));
+    let transfers = decode(transfers)
+        .filter_map(CreateTransfersIndividualApiError::from_raw_result)
+        .map(|e| CreateErrorKind::Transfer(e.kind()));
+    accounts.chain(transfers)
+}
+
+#[cfg(test)]
+mod decode_into_spec {
+    use crate::Account;
+
+    use super::decode_into;
+
+    #[test]
+    fn reuses_provided_buffer() {
+        let accounts = [Account::new(1, 2, 3), Account::new(4, 5, 6)];
+        let payload = bytemuck::cast_slice::<_, u8>(&accounts);
+
+        let mut buf = Vec::with_capacity(16);
+        buf.push(Account::new(7, 8, 9));
+        let buf_ptr = buf.as_ptr();
+
+        let out = decode_into(buf, payload);
+
+        assert_eq!(out.as_ptr(), buf_ptr, "buffer must be reused");
+        assert_eq!(out.iter().map(Account::id).collect::<Vec<_>>(), [1, 4]);
+    }
+}
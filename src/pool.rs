@@ -0,0 +1,183 @@
+//! Pool of [`Client`] sessions to the same cluster.
+
+use std::{
+    num::NonZeroUsize,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use core::{
+    error::{CreateAccountsError, CreateTransfersError, NewClientError, SendError},
+    util::{RawConstPtr, SendOwnedSlice},
+};
+
+use crate::{account, operation, Account, Client, Operation, QueryFilter, Transfer};
+
+/// Pool of [`Client`] sessions to the same cluster, spreading requests across them.
+///
+/// Every request is submitted via the least loaded session (the one with the fewest requests in
+/// flight), taking the sessions in a round-robin order to break ties.
+///
+/// Dropping the pool shuts all its sessions down together.
+pub struct ClientPool {
+    sessions: Box<[Client]>,
+    next: AtomicUsize,
+}
+
+impl ClientPool {
+    /// Creates a new [`ClientPool`] of the provided number of `sessions`.
+    pub fn new<A>(
+        cluster_id: u128,
+        address: A,
+        sessions: NonZeroUsize,
+    ) -> Result<Self, NewClientError>
+    where
+        A: AsRef<[u8]>,
+    {
+        let address = address.as_ref();
+        Ok(Self {
+            sessions: (0..sessions.get())
+                .map(|_| Client::new(cluster_id, address))
+                .collect::<Result<_, _>>()?,
+            next: AtomicUsize::new(0),
+        })
+    }
+
+    /// Returns all the sessions of this [`ClientPool`].
+    pub fn sessions(&self) -> &[Client] {
+        &self.sessions
+    }
+
+    /// Returns the total number of requests in flight across all the sessions.
+    pub fn in_flight(&self) -> usize {
+        self.sessions.iter().map(Client::in_flight).sum()
+    }
+
+    /// Picks the least loaded session to submit the next request via.
+    pub fn session(&self) -> &Client {
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        &self.sessions[least_loaded(start, self.sessions.len(), |i| self.sessions[i].in_flight())]
+    }
+
+    /// Executes the provided [`Operation`] via the least loaded session.
+    pub async fn execute<Op>(&self, input: impl Into<Op::Input>) -> Result<Op::Output, Op::Error>
+    where
+        Op: Operation + 'static,
+    {
+        self.session().execute::<Op>(input).await
+    }
+
+    pub async fn create_accounts<T>(&self, accounts: T) -> Result<(), CreateAccountsError>
+    where
+        T: Into<SendOwnedSlice<Account>>,
+    {
+        self.execute::<operation::CreateAccounts>(accounts).await
+    }
+
+    pub async fn create_transfers<T>(&self, transfers: T) -> Result<(), CreateTransfersError>
+    where
+        T: Into<SendOwnedSlice<Transfer>>,
+    {
+        self.execute::<operation::CreateTransfers>(transfers).await
+    }
+
+    pub async fn get_account_balances<T>(
+        &self,
+        filter: T,
+    ) -> Result<Vec<account::Balance>, SendError>
+    where
+        T: RawConstPtr<Target = account::Filter> + Send + 'static,
+    {
+        self.execute::<operation::GetAccountBalances>(SendOwnedSlice::from_single(filter))
+            .await
+    }
+
+    pub async fn get_account_transfers<T>(&self, filter: T) -> Result<Vec<Transfer>, SendError>
+    where
+        T: RawConstPtr<Target = account::Filter> + Send + 'static,
+    {
+        self.execute::<operation::GetAccountTransfers>(SendOwnedSlice::from_single(filter))
+            .await
+    }
+
+    pub async fn lookup_accounts<T>(&self, ids: T) -> Result<Vec<Account>, SendError>
+    where
+        T: Into<SendOwnedSlice<u128>>,
+    {
+        self.execute::<operation::LookupAccounts>(ids).await
+    }
+
+    pub async fn lookup_transfers<T>(&self, ids: T) -> Result<Vec<Transfer>, SendError>
+    where
+        T: Into<SendOwnedSlice<u128>>,
+    {
+        self.execute::<operation::LookupTransfers>(ids).await
+    }
+
+    pub async fn query_accounts<T>(&self, filter: T) -> Result<Vec<Account>, SendError>
+    where
+        T: RawConstPtr<Target = QueryFilter> + Send + 'static,
+    {
+        self.execute::<operation::QueryAccounts>(SendOwnedSlice::from_single(filter))
+            .await
+    }
+
+    pub async fn query_transfers<T>(&self, filter: T) -> Result<Vec<Transfer>, SendError>
+    where
+        T: RawConstPtr<Target = QueryFilter> + Send + 'static,
+    {
+        self.execute::<operation::QueryTransfers>(SendOwnedSlice::from_single(filter))
+            .await
+    }
+}
+
+/// Returns the index of the least loaded out of `len` sessions, given the `load` of each one by
+/// its index.
+///
+/// Sessions are taken in a round-robin order beginning from the `start` one, so the first of the
+/// equally loaded sessions in this order is picked.
+fn least_loaded(start: usize, len: usize, load: impl Fn(usize) -> usize) -> usize {
+    let start = start % len;
+    // PANIC: Unwrapping is OK here, because there is at least one session.
+    (start..start + len)
+        .map(|i| i % len)
+        .min_by_key(|i| load(*i))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod pool_spec {
+    use super::least_loaded;
+
+    #[test]
+    fn rotates_equally_loaded_sessions() {
+        let picked = (0..4)
+            .map(|start| least_loaded(start, 3, |_| 0))
+            .collect::<Vec<_>>();
+
+        assert_eq!(picked, [0, 1, 2, 0]);
+    }
+
+    #[test]
+    fn picks_least_loaded_session() {
+        let loads = [2, 0, 1];
+
+        for start in 0..3 {
+            assert_eq!(least_loaded(start, 3, |i| loads[i]), 1);
+        }
+    }
+
+    #[test]
+    fn rotates_among_least_loaded_sessions_only() {
+        let loads = [1, 0, 0];
+
+        assert_eq!(least_loaded(0, 3, |i| loads[i]), 1);
+        assert_eq!(least_loaded(1, 3, |i| loads[i]), 1);
+        assert_eq!(least_loaded(2, 3, |i| loads[i]), 2);
+    }
+
+    #[test]
+    fn survives_counter_wrapping() {
+        assert_eq!(least_loaded(usize::MAX, 3, |_| 0), usize::MAX % 3);
+        assert_eq!(least_loaded(0, 3, |_| 0), 0);
+    }
+}
@@ -0,0 +1,410 @@
+//! Routing of requests across multiple clusters, partitioned by ledger.
+
+use std::{collections::HashMap, error::Error, fmt, hash::Hash};
+
+use core::{
+    error::{
+        CreateAccountsApiError, CreateAccountsError, CreateAccountsIndividualApiError,
+        CreateTransfersApiError, CreateTransfersError, CreateTransfersIndividualApiError,
+        RawCreateAccountsIndividualApiResult, RawCreateTransfersIndividualApiResult, SendError,
+    },
+    util::SendOwnedSlice,
+};
+
+use crate::{join::join_all, Account, Client, Transfer};
+
+/// Router of requests across multiple clusters, partitioned by ledger.
+///
+/// Holds a [`Client`] for every cluster keyed by `K`, and routes created [`Account`]s and
+/// [`Transfer`]s to the cluster returned by the `route` function for their ledger. Lookups by ID
+/// are fanned out to all the clusters, as the owning one is unknown. Use
+/// [`ClusterRouter::cluster()`] to access a specific cluster directly.
+pub struct ClusterRouter<K, F> {
+    clusters: HashMap<K, Client>,
+    route: F,
+}
+
+/// Error of creating events via a [`ClusterRouter`].
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum RouteError<E> {
+    /// Event with the `index` in the batch has the `ledger` routed to an unknown cluster.
+    ///
+    /// None of the events is submitted.
+    UnknownCluster { index: u32, ledger: u32 },
+
+    /// Cluster failed to create its part of the events.
+    Cluster(E),
+}
+
+/// Part of a mixed batch, routed to a single cluster.
+struct Batch<'c, C, T> {
+    client: &'c C,
+    events: Vec<T>,
+    /// Indices of the `events` in the original batch.
+    indices: Vec<u32>,
+}
+
+impl<K, F> ClusterRouter<K, F>
+where
+    K: Eq + Hash,
+    F: Fn(u32) -> K,
+{
+    /// Creates a new [`ClusterRouter`] out of the provided `clusters`, routing events by their
+    /// ledger with the provided `route` function.
+    pub fn new<I>(clusters: I, route: F) -> Self
+    where
+        I: IntoIterator<Item = (K, Client)>,
+    {
+        Self {
+            clusters: clusters.into_iter().collect(),
+            route,
+        }
+    }
+
+    /// Returns the [`Client`] of the cluster with the provided `key`, if any.
+    pub fn cluster(&self, key: &K) -> Option<&Client> {
+        self.clusters.get(key)
+    }
+
+    /// Returns the [`Client`] of the cluster owning the provided `ledger`, if any.
+    pub fn cluster_of(&self, ledger: u32) -> Option<&Client> {
+        self.clusters.get(&(self.route)(ledger))
+    }
+
+    /// Creates the provided `accounts`, splitting them per cluster by their ledger.
+    ///
+    /// Individual errors of all the clusters are merged, referring the indices of the original
+    /// `accounts`. If any cluster fails to process its part, its [`SendError`] is returned
+    /// instead, while the other parts may still be created.
+    ///
+    /// Fails with [`RouteError::UnknownCluster`] without submitting anything, if any of the
+    /// `accounts` is routed to an unknown cluster.
+    pub async fn create_accounts<T>(
+        &self,
+        accounts: T,
+    ) -> Result<(), RouteError<CreateAccountsError>>
+    where
+        T: Into<SendOwnedSlice<Account>>,
+    {
+        let accounts = accounts.into();
+        let (indices, requests): (Vec<_>, Vec<_>) = self
+            .split(accounts.as_slice(), Account::ledger)?
+            .into_iter()
+            .map(|b| (b.indices, b.client.create_accounts(b.events)))
+            .unzip();
+
+        let mut errors = Vec::new();
+        for (indices, result) in indices.into_iter().zip(join_all(requests).await) {
+            match result {
+                Ok(()) => {}
+                Err(CreateAccountsError::Api(e)) => errors.extend(remap_accounts(&indices, &e)),
+                Err(e) => return Err(RouteError::Cluster(e)),
+            }
+        }
+        errors.sort_by_key(CreateAccountsIndividualApiError::index);
+        CreateAccountsApiError::from_errors(errors)
+            .map_or(Ok(()), |e| Err(RouteError::Cluster(e.into())))
+    }
+
+    /// Creates the provided `transfers`, splitting them per cluster by their ledger.
+    ///
+    /// Individual errors of all the clusters are merged, referring the indices of the original
+    /// `transfers`. If any cluster fails to process its part, its [`SendError`] is returned
+    /// instead, while the other parts may still be created.
+    ///
+    /// Fails with [`RouteError::UnknownCluster`] without submitting anything, if any of the
+    /// `transfers` is routed to an unknown cluster.
+    pub async fn create_transfers<T>(
+        &self,
+        transfers: T,
+    ) -> Result<(), RouteError<CreateTransfersError>>
+    where
+        T: Into<SendOwnedSlice<Transfer>>,
+    {
+        let transfers = transfers.into();
+        let (indices, requests): (Vec<_>, Vec<_>) = self
+            .split(transfers.as_slice(), Transfer::ledger)?
+            .into_iter()
+            .map(|b| (b.indices, b.client.create_transfers(b.events)))
+            .unzip();
+
+        let mut errors = Vec::new();
+        for (indices, result) in indices.into_iter().zip(join_all(requests).await) {
+            match result {
+                Ok(()) => {}
+                Err(CreateTransfersError::Api(e)) => errors.extend(remap_transfers(&indices, &e)),
+                Err(e) => return Err(RouteError::Cluster(e)),
+            }
+        }
+        errors.sort_by_key(CreateTransfersIndividualApiError::index);
+        CreateTransfersApiError::from_errors(errors)
+            .map_or(Ok(()), |e| Err(RouteError::Cluster(e.into())))
+    }
+
+    /// Looks up [`Account`]s with the provided `ids` in all the clusters.
+    ///
+    /// Found [`Account`]s are returned in the order of the `ids`.
+    pub async fn lookup_accounts<T>(&self, ids: T) -> Result<Vec<Account>, SendError>
+    where
+        T: Into<SendOwnedSlice<u128>>,
+    {
+        let ids = ids.into();
+        let requests = self
+            .clusters
+            .values()
+            .map(|c| c.lookup_accounts(ids.as_slice().to_vec()))
+            .collect::<Vec<_>>();
+        let found = join_all(requests).await;
+        merge_found(ids.as_slice(), found, Account::id)
+    }
+
+    /// Looks up [`Transfer`]s with the provided `ids` in all the clusters.
+    ///
+    /// Found [`Transfer`]s are returned in the order of the `ids`.
+    pub async fn lookup_transfers<T>(&self, ids: T) -> Result<Vec<Transfer>, SendError>
+    where
+        T: Into<SendOwnedSlice<u128>>,
+    {
+        let ids = ids.into();
+        let requests = self
+            .clusters
+            .values()
+            .map(|c| c.lookup_transfers(ids.as_slice().to_vec()))
+            .collect::<Vec<_>>();
+        let found = join_all(requests).await;
+        merge_found(ids.as_slice(), found, Transfer::id)
+    }
+
+    /// Splits the provided `events` into [`Batch`]es per cluster by their `ledger`.
+    fn split<T, E>(
+        &self,
+        events: &[T],
+        ledger: impl Fn(&T) -> u32,
+    ) -> Result<Vec<Batch<'_, Client, T>>, RouteError<E>>
+    where
+        T: Copy,
+    {
+        split(events, ledger, |l| {
+            self.clusters.get_key_value(&(self.route)(l))
+        })
+    }
+}
+
+/// Splits the provided `events` into [`Batch`]es per cluster returned by the `cluster` function
+/// for their `ledger`.
+fn split<'c, K, C, T, E>(
+    events: &[T],
+    ledger: impl Fn(&T) -> u32,
+    cluster: impl Fn(u32) -> Option<(&'c K, &'c C)>,
+) -> Result<Vec<Batch<'c, C, T>>, RouteError<E>>
+where
+    K: Eq + Hash + 'c,
+    T: Copy,
+{
+    let mut batches = HashMap::<&K, Batch<'_, C, T>>::new();
+    for (i, event) in events.iter().enumerate() {
+        let index = i as u32;
+        let ledger = ledger(event);
+        let Some((key, client)) = cluster(ledger) else {
+            return Err(RouteError::UnknownCluster { index, ledger });
+        };
+        let batch = batches.entry(key).or_insert_with(|| Batch {
+            client,
+            events: Vec::new(),
+            indices: Vec::new(),
+        });
+        batch.events.push(*event);
+        batch.indices.push(index);
+    }
+    Ok(batches.into_values().collect())
+}
+
+/// Remaps the individual errors of a [`Batch`] to the `indices` of the events in the original
+/// batch.
+fn remap_accounts<'e>(
+    indices: &'e [u32],
+    errors: &'e CreateAccountsApiError,
+) -> impl Iterator<Item = CreateAccountsIndividualApiError> + 'e {
+    errors.as_slice().iter().map(|e| {
+        let raw = RawCreateAccountsIndividualApiResult {
+            index: indices[e.index() as usize],
+            result: e.inner().code().get(),
+        };
+        // PANIC: Unwrapping is OK here, because the `result` code is non-zero.
+        CreateAccountsIndividualApiError::from_raw_result(raw).unwrap()
+    })
+}
+
+/// Remaps the individual errors of a [`Batch`] to the `indices` of the events in the original
+/// batch.
+fn remap_transfers<'e>(
+    indices: &'e [u32],
+    errors: &'e CreateTransfersApiError,
+) -> impl Iterator<Item = CreateTransfersIndividualApiError> + 'e {
+    errors.as_slice().iter().map(|e| {
+        let raw = RawCreateTransfersIndividualApiResult {
+            index: indices[e.index() as usize],
+            result: e.inner().code().get(),
+        };
+        // PANIC: Unwrapping is OK here, because the `result` code is non-zero.
+        CreateTransfersIndividualApiError::from_raw_result(raw).unwrap()
+    })
+}
+
+/// Merges the `found` events of all the clusters in the order of the provided `ids`.
+fn merge_found<T>(
+    ids: &[u128],
+    found: Vec<Result<Vec<T>, SendError>>,
+    id: impl Fn(&T) -> u128,
+) -> Result<Vec<T>, SendError> {
+    let mut positions = HashMap::with_capacity(ids.len());
+    for (i, id) in ids.iter().enumerate() {
+        positions.entry(*id).or_insert(i);
+    }
+    let mut merged = found
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    merged.sort_by_key(|e| positions.get(&id(e)).copied());
+    Ok(merged)
+}
+
+impl<E> Error for RouteError<E>
+where
+    E: Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::UnknownCluster { .. } => None,
+            Self::Cluster(e) => Some(e as _),
+        }
+    }
+}
+
+impl<E> fmt::Display for RouteError<E>
+where
+    E: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownCluster { index, ledger } => write!(
+                f,
+                "Failed to route event with index {index}: ledger {ledger} is routed to an \
+                 unknown cluster",
+            ),
+            Self::Cluster(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl<E> From<E> for RouteError<E> {
+    fn from(value: E) -> Self {
+        Self::Cluster(value)
+    }
+}
+
+fn _test_thread_safe(
+    router: ClusterRouter<u8, fn(u32) -> u8>,
+    accounts: Vec<Account>,
+    ids: Vec<u128>,
+) {
+    check_thread_safe(async move {
+        router.create_accounts(accounts).await.unwrap();
+        router.lookup_accounts(ids).await.unwrap();
+    });
+
+    fn check_thread_safe<T>(_: T)
+    where
+        T: Send + Sync + 'static,
+    {
+    }
+}
+
+#[cfg(test)]
+mod router_spec {
+    use core::error::{
+        CreateAccountsApiError, CreateAccountsIndividualApiError,
+        RawCreateAccountsIndividualApiResult, SendError,
+    };
+
+    use super::{merge_found, remap_accounts, split, RouteError};
+
+    #[test]
+    fn splits_events_per_cluster() {
+        let clusters = [(0_u8, "a"), (1, "b")];
+        let events = [10_u32, 21, 11, 20];
+
+        let mut batches = split::<_, _, _, ()>(
+            &events,
+            |e| *e,
+            |l| clusters.get(l as usize / 10 - 1).map(|(k, c)| (k, c)),
+        )
+        .unwrap();
+        batches.sort_by_key(|b| *b.client);
+
+        assert_eq!(batches.len(), 2);
+        assert_eq!(
+            (batches[0].events.as_slice(), batches[0].indices.as_slice()),
+            (&[10, 11][..], &[0, 2][..])
+        );
+        assert_eq!(
+            (batches[1].events.as_slice(), batches[1].indices.as_slice()),
+            (&[21, 20][..], &[1, 3][..])
+        );
+    }
+
+    #[test]
+    fn fails_on_unknown_cluster() {
+        let clusters = [(0_u8, "a")];
+
+        let res = split::<_, _, _, ()>(
+            &[1_u32, 2, 3],
+            |e| *e,
+            |l| {
+                clusters
+                    .iter()
+                    .find(|(k, _)| u32::from(*k) + 1 == l)
+                    .map(|(k, c)| (k, c))
+            },
+        );
+
+        assert!(matches!(
+            res,
+            Err(RouteError::UnknownCluster {
+                index: 1,
+                ledger: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn remaps_error_indices_to_original_batch() {
+        let errors = [(0, 21), (1, 22)]
+            .map(|(index, result)| {
+                CreateAccountsIndividualApiError::from_raw_result(
+                    RawCreateAccountsIndividualApiResult { index, result },
+                )
+                .unwrap()
+            })
+            .to_vec();
+        let errors = CreateAccountsApiError::from_errors(errors).unwrap();
+
+        let remapped = remap_accounts(&[3, 5], &errors)
+            .map(|e| (e.index(), e.inner().code().get()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(remapped, [(3, 21), (5, 22)]);
+    }
+
+    #[test]
+    fn merges_found_in_ids_order() {
+        let found: Vec<Result<Vec<u128>, SendError>> = vec![Ok(vec![3, 1]), Ok(vec![2])];
+
+        let merged = merge_found(&[1, 2, 3, 4], found, |id| *id).unwrap();
+
+        assert_eq!(merged, [1, 2, 3]);
+    }
+}
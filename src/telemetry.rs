@@ -0,0 +1,188 @@
+//! Recording of submitted requests via the [`metrics`] facade.
+//!
+//! Recorded metrics:
+//! - `tigerbeetle_client_requests_in_flight` gauge of submitted requests not completed yet;
+//! - `tigerbeetle_client_request_duration_seconds` histogram of time between submitting a request
+//!   and receiving its reply, labeled by `operation`;
+//! - `tigerbeetle_client_batch_size` histogram of events submitted in a single request, labeled
+//!   by `operation`;
+//! - `tigerbeetle_client_send_errors_total` counter of failed requests, labeled by `operation` and
+//!   [`SendErrorKind`] as `kind`;
+//! - `tigerbeetle_client_create_account_errors_total` counter of failed events, labeled by
+//!   [`CreateAccountErrorKind`] as `kind`;
+//! - `tigerbeetle_client_create_transfer_errors_total` counter of failed events, labeled by
+//!   [`CreateTransferErrorKind`] as `kind`.
+//!
+//! [`CreateAccountErrorKind`]: core::error::CreateAccountErrorKind
+//! [`CreateTransferErrorKind`]: core::error::CreateTransferErrorKind
+//! [`SendErrorKind`]: core::error::SendErrorKind
+
+use std::{fmt, time::Instant};
+
+use core::{error::SendError, util::SendAsBytesOwnedSlice};
+use metrics::{Counter, Gauge, Histogram, Key, Label, Level, Metadata};
+
+use crate::operation::{self, CreateErrorKind};
+
+/// [`Metadata`] of all the recorded metrics.
+///
+/// The [`metrics`] macros cannot be used here, as they refer to `::core` unhygienically, which
+/// resolves to the `core` crate of this workspace instead.
+static METADATA: Metadata<'static> =
+    Metadata::new(module_path!(), Level::INFO, Some(module_path!()));
+
+/// Wraps the provided `complete` callback into recording metrics of the request.
+pub(crate) fn record<F>(
+    operation: core::Operation,
+    data: &SendAsBytesOwnedSlice,
+    complete: F,
+) -> impl FnOnce(Result<core::Reply<'_>, SendError>) + Send + 'static
+where
+    F: FnOnce(Result<core::Reply<'_>, SendError>) + Send + 'static,
+{
+    let op = Label::new("operation", debug_label(operation.kind()));
+    histogram("tigerbeetle_client_batch_size", vec![op.clone()]).record(data.len() as f64);
+    let in_flight = gauge("tigerbeetle_client_requests_in_flight");
+    in_flight.increment(1.0);
+    let submitted_at = Instant::now();
+    move |reply| {
+        in_flight.decrement(1.0);
+        histogram(
+            "tigerbeetle_client_request_duration_seconds",
+            vec![op.clone()],
+        )
+        .record(submitted_at.elapsed());
+        match &reply {
+            Ok(reply) => record_errors(operation, reply.payload),
+            Err(e) => counter(
+                "tigerbeetle_client_send_errors_total",
+                vec![op, Label::new("kind", debug_label(e.kind()))],
+            )
+            .increment(1),
+        }
+        complete(reply);
+    }
+}
+
+/// Records individual errors contained in the reply `payload` of the provided `operation`.
+fn record_errors(operation: core::Operation, payload: &[u8]) {
+    for error in operation::create_errors(operation, payload) {
+        let (name, kind) = match error {
+            CreateErrorKind::Account(kind) => (
+                "tigerbeetle_client_create_account_errors_total",
+                debug_label(kind),
+            ),
+            CreateErrorKind::Transfer(kind) => (
+                "tigerbeetle_client_create_transfer_errors_total",
+                debug_label(kind),
+            ),
+        };
+        counter(name, vec![Label::new("kind", kind)]).increment(1);
+    }
+}
+
+fn counter(name: &'static str, labels: Vec<Label>) -> Counter {
+    let key = Key::from_parts(name, labels);
+    metrics::with_recorder(|r| r.register_counter(&key, &METADATA))
+}
+
+fn gauge(name: &'static str) -> Gauge {
+    let key = Key::from_static_name(name);
+    metrics::with_recorder(|r| r.register_gauge(&key, &METADATA))
+}
+
+fn histogram(name: &'static str, labels: Vec<Label>) -> Histogram {
+    let key = Key::from_parts(name, labels);
+    metrics::with_recorder(|r| r.register_histogram(&key, &METADATA))
+}
+
+fn debug_label(kind: impl fmt::Debug) -> String {
+    format!("{kind:?}")
+}
+
+#[cfg(test)]
+mod telemetry_spec {
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    };
+
+    use core::{
+        error::{CreateAccountsIndividualApiError, RawCreateAccountsIndividualApiResult},
+        OperationKind,
+    };
+    use metrics::{
+        Counter, CounterFn, Gauge, Histogram, Key, KeyName, Label, Metadata, Recorder,
+        SharedString, Unit,
+    };
+
+    use super::record_errors;
+
+    type Counts = Arc<Mutex<HashMap<Key, u64>>>;
+
+    /// [`Recorder`] remembering values of its counters.
+    #[derive(Default)]
+    struct CountingRecorder(Counts);
+
+    struct CountingCounter(Key, Counts);
+
+    impl CounterFn for CountingCounter {
+        fn increment(&self, value: u64) {
+            *self.1.lock().unwrap().entry(self.0.clone()).or_default() += value;
+        }
+
+        fn absolute(&self, value: u64) {
+            self.1.lock().unwrap().insert(self.0.clone(), value);
+        }
+    }
+
+    impl Recorder for CountingRecorder {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            Counter::from_arc(Arc::new(CountingCounter(key.clone(), Arc::clone(&self.0))))
+        }
+
+        fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+            Gauge::noop()
+        }
+
+        fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram {
+            Histogram::noop()
+        }
+    }
+
+    #[test]
+    fn counts_failed_create_results_by_kind() {
+        let failed = RawCreateAccountsIndividualApiResult {
+            index: 1,
+            result: 21,
+        };
+        let results = [
+            RawCreateAccountsIndividualApiResult {
+                index: 0,
+                result: 0,
+            },
+            failed,
+            RawCreateAccountsIndividualApiResult { index: 2, ..failed },
+        ];
+        let payload = bytemuck::cast_slice::<_, u8>(&results);
+        let recorder = CountingRecorder::default();
+
+        metrics::with_local_recorder(&recorder, || {
+            record_errors(OperationKind::CreateAccounts.into(), payload);
+            record_errors(OperationKind::LookupAccounts.into(), payload);
+        });
+
+        let kind = CreateAccountsIndividualApiError::from_raw_result(failed)
+            .unwrap()
+            .kind();
+        let key = Key::from_parts(
+            "tigerbeetle_client_create_account_errors_total",
+            vec![Label::new("kind", format!("{kind:?}"))],
+        );
+        assert_eq!(*recorder.0.lock().unwrap(), HashMap::from([(key, 2)]));
+    }
+}
@@ -0,0 +1,122 @@
+//! [`tracing`] instrumentation of submitted requests.
+
+use std::time::{Duration, Instant};
+
+use core::{error::SendError, util::SendAsBytesOwnedSlice};
+use tracing::{field, Span};
+
+use crate::operation;
+
+/// Creates a [`Span`] describing a request of the provided `operation`.
+///
+/// The whole request [`Future`] is instrumented with it, so the [`Span`] covers waiting for the
+/// admission of the request (see [`Client::with_max_in_flight()`]), the request being processed
+/// by the client and the cluster, and the awaiting [`Future`] being resumed on its executor once
+/// the reply is received. Each of these phases is recorded separately, as `admission_us`,
+/// `latency_us` and `resume_us` respectively.
+///
+/// [`Client::with_max_in_flight()`]: crate::Client::with_max_in_flight
+/// [`Future`]: std::future::Future
+pub(crate) fn span(operation: core::Operation) -> Span {
+    tracing::info_span!(
+        "tigerbeetle.request",
+        operation = ?operation,
+        batch_size = field::Empty,
+        payload_bytes = field::Empty,
+        reply_bytes = field::Empty,
+        status = field::Empty,
+        errors = field::Empty,
+        admission_us = field::Empty,
+        latency_us = field::Empty,
+        resume_us = field::Empty,
+    )
+}
+
+/// Records the time the current request waited for its admission.
+pub(crate) fn record_admission(waited: Duration) {
+    Span::current().record("admission_us", waited.as_micros() as u64);
+}
+
+/// Records the time between the reply of the current request being received and its awaiting
+/// [`Future`] being resumed, which is spent on the caller's executor.
+///
+/// [`Future`]: std::future::Future
+pub(crate) fn record_resume(received_at: Instant) {
+    let delay = received_at.elapsed();
+    Span::current().record("resume_us", delay.as_micros() as u64);
+    tracing::debug!(?delay, "request resumed");
+}
+
+/// Wraps the provided `complete` callback into recording the outcome of the request into the
+/// current [`Span`] (created by the [`span()`]).
+pub(crate) fn instrument<F>(
+    operation: core::Operation,
+    data: &SendAsBytesOwnedSlice,
+    complete: F,
+) -> impl FnOnce(Result<core::Reply<'_>, SendError>) + Send + 'static
+where
+    F: FnOnce(Result<core::Reply<'_>, SendError>) + Send + 'static,
+{
+    let span = Span::current();
+    span.record("batch_size", data.len());
+    span.record("payload_bytes", data.as_bytes().len());
+    let submitted_at = Instant::now();
+    move |reply| {
+        let _entered = span.enter();
+        // Time between the request being submitted and its completion callback being called.
+        let latency = submitted_at.elapsed();
+        span.record("latency_us", latency.as_micros() as u64);
+        match &reply {
+            Ok(reply) => {
+                let errors = count_errors(operation, reply.payload);
+                span.record("reply_bytes", reply.payload.len());
+                span.record("status", "ok");
+                span.record("errors", errors);
+                tracing::debug!(?latency, errors, "request completed");
+            }
+            Err(e) => {
+                span.record("status", field::display(e));
+                tracing::debug!(?latency, error = %e, "request failed");
+            }
+        }
+        complete(reply);
+    }
+}
+
+/// Counts failed events in the reply `payload` of the provided `operation`.
+///
+/// Returns `0` for operations not reporting individual errors.
+fn count_errors(operation: core::Operation, payload: &[u8]) -> usize {
+    operation::create_errors(operation, payload).count()
+}
+
+#[cfg(test)]
+mod count_errors_spec {
+    use core::{error::RawCreateAccountsIndividualApiResult, OperationKind};
+
+    use super::count_errors;
+
+    #[test]
+    fn counts_only_failed_create_results() {
+        let results = [
+            RawCreateAccountsIndividualApiResult {
+                index: 0,
+                result: 0,
+            },
+            RawCreateAccountsIndividualApiResult {
+                index: 1,
+                result: 21,
+            },
+        ];
+        let payload = bytemuck::cast_slice::<_, u8>(&results);
+
+        assert_eq!(
+            count_errors(OperationKind::CreateAccounts.into(), payload),
+            1
+        );
+        assert_eq!(
+            count_errors(OperationKind::LookupAccounts.into(), payload),
+            0
+        );
+    }
+}
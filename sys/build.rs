@@ -20,6 +20,511 @@ const TIGERBEETLE_RELEASE: &str = "0.16.78";
 /// Commit hash of the [`TIGERBEETLE_RELEASE`].
 const TIGERBEETLE_COMMIT: &str = "c3d9b09dc88e94dde9ac915c6e94a4c650332080";
 
+/// Expected ABI layout of a `tb_client.h` struct.
+struct StructLayout {
+    name: &'static str,
+    size: usize,
+    align: usize,
+    /// Names and offsets of the fields, in the declaration order.
+    fields: &'static [(&'static str, usize)],
+}
+
+/// Expected ABI layout of the `tb_client.h` structs.
+///
+/// Maintained by hand after the upstream [data model] of the [`TIGERBEETLE_RELEASE`], as the
+/// header doesn't assert the layout itself. The generated bindings are checked against it at
+/// compile time, so it must be updated whenever an upgrade changes the layout.
+///
+/// [data model]: https://docs.tigerbeetle.com/reference
+const ABI_LAYOUT: &[StructLayout] = &[
+    StructLayout {
+        name: "tb_account_t",
+        size: 128,
+        align: 16,
+        fields: &[
+            ("id", 0),
+            ("debits_pending", 16),
+            ("debits_posted", 32),
+            ("credits_pending", 48),
+            ("credits_posted", 64),
+            ("user_data_128", 80),
+            ("user_data_64", 96),
+            ("user_data_32", 104),
+            ("reserved", 108),
+            ("ledger", 112),
+            ("code", 116),
+            ("flags", 118),
+            ("timestamp", 120),
+        ],
+    },
+    StructLayout {
+        name: "tb_transfer_t",
+        size: 128,
+        align: 16,
+        fields: &[
+            ("id", 0),
+            ("debit_account_id", 16),
+            ("credit_account_id", 32),
+            ("amount", 48),
+            ("pending_id", 64),
+            ("user_data_128", 80),
+            ("user_data_64", 96),
+            ("user_data_32", 104),
+            ("timeout", 108),
+            ("ledger", 112),
+            ("code", 116),
+            ("flags", 118),
+            ("timestamp", 120),
+        ],
+    },
+    StructLayout {
+        name: "tb_account_filter_t",
+        size: 128,
+        align: 16,
+        fields: &[
+            ("account_id", 0),
+            ("user_data_128", 16),
+            ("user_data_64", 32),
+            ("user_data_32", 40),
+            ("code", 44),
+            ("reserved", 46),
+            ("timestamp_min", 104),
+            ("timestamp_max", 112),
+            ("limit", 120),
+            ("flags", 124),
+        ],
+    },
+    StructLayout {
+        name: "tb_account_balance_t",
+        size: 128,
+        align: 16,
+        fields: &[
+            ("debits_pending", 0),
+            ("debits_posted", 16),
+            ("credits_pending", 32),
+            ("credits_posted", 48),
+            ("timestamp", 64),
+            ("reserved", 72),
+        ],
+    },
+    StructLayout {
+        name: "tb_query_filter_t",
+        size: 64,
+        align: 16,
+        fields: &[
+            ("user_data_128", 0),
+            ("user_data_64", 16),
+            ("user_data_32", 24),
+            ("ledger", 28),
+            ("code", 32),
+            ("reserved", 34),
+            ("timestamp_min", 40),
+            ("timestamp_max", 48),
+            ("limit", 56),
+            ("flags", 60),
+        ],
+    },
+    StructLayout {
+        name: "tb_create_accounts_result_t",
+        size: 8,
+        align: 4,
+        fields: &[("index", 0), ("result", 4)],
+    },
+    StructLayout {
+        name: "tb_create_transfers_result_t",
+        size: 8,
+        align: 4,
+        fields: &[("index", 0), ("result", 4)],
+    },
+];
+
+/// Descriptions of the `tb_client.h` result codes, keyed by their names.
+///
+/// The header has no docs for them, so these are adapted from the upstream [`create_accounts`] and
+/// [`create_transfers`] reference of the [`TIGERBEETLE_RELEASE`]. A result code missing here fails
+/// the build, so new ones are described on upgrades.
+///
+/// [`create_accounts`]: https://docs.tigerbeetle.com/reference/requests/create_accounts
+/// [`create_transfers`]: https://docs.tigerbeetle.com/reference/requests/create_transfers
+const RESULT_DESCRIPTIONS: &[(&str, &str)] = &[
+    (
+        "TB_CREATE_ACCOUNT_LINKED_EVENT_FAILED",
+        "The account was not created, because another account in its linked chain failed",
+    ),
+    (
+        "TB_CREATE_ACCOUNT_LINKED_EVENT_CHAIN_OPEN",
+        "The account was not created, because it's the last one in the batch and has the `linked` flag set, leaving the chain open",
+    ),
+    (
+        "TB_CREATE_ACCOUNT_IMPORTED_EVENT_EXPECTED",
+        "The account was not created, because the first account of the batch is imported, so all of them must have the `imported` flag set",
+    ),
+    (
+        "TB_CREATE_ACCOUNT_IMPORTED_EVENT_NOT_EXPECTED",
+        "The account was not created, because the first account of the batch is not imported, so none of them may have the `imported` flag set",
+    ),
+    (
+        "TB_CREATE_ACCOUNT_TIMESTAMP_MUST_BE_ZERO",
+        "The account was not created, because its timestamp is not zero, while it's set by the cluster for accounts not imported",
+    ),
+    (
+        "TB_CREATE_ACCOUNT_IMPORTED_EVENT_TIMESTAMP_OUT_OF_RANGE",
+        "The imported account was not created, because its timestamp is out of range, while it must be greater than zero and less than 2^63",
+    ),
+    (
+        "TB_CREATE_ACCOUNT_IMPORTED_EVENT_TIMESTAMP_MUST_NOT_ADVANCE",
+        "The imported account was not created, because its timestamp is greater than the current cluster time, while it must be a past one",
+    ),
+    (
+        "TB_CREATE_ACCOUNT_RESERVED_FIELD",
+        "The account was not created, because its reserved field is not zero",
+    ),
+    (
+        "TB_CREATE_ACCOUNT_RESERVED_FLAG",
+        "The account was not created, because one of its reserved flags is set",
+    ),
+    (
+        "TB_CREATE_ACCOUNT_ID_MUST_NOT_BE_ZERO",
+        "The account was not created, because its ID is zero, which is a reserved value",
+    ),
+    (
+        "TB_CREATE_ACCOUNT_ID_MUST_NOT_BE_INT_MAX",
+        "The account was not created, because its ID is 2^128 - 1, which is a reserved value",
+    ),
+    (
+        "TB_CREATE_ACCOUNT_EXISTS_WITH_DIFFERENT_FLAGS",
+        "An account with the same ID already exists, but with different flags",
+    ),
+    (
+        "TB_CREATE_ACCOUNT_EXISTS_WITH_DIFFERENT_USER_DATA_128",
+        "An account with the same ID already exists, but with a different `user_data_128`",
+    ),
+    (
+        "TB_CREATE_ACCOUNT_EXISTS_WITH_DIFFERENT_USER_DATA_64",
+        "An account with the same ID already exists, but with a different `user_data_64`",
+    ),
+    (
+        "TB_CREATE_ACCOUNT_EXISTS_WITH_DIFFERENT_USER_DATA_32",
+        "An account with the same ID already exists, but with a different `user_data_32`",
+    ),
+    (
+        "TB_CREATE_ACCOUNT_EXISTS_WITH_DIFFERENT_LEDGER",
+        "An account with the same ID already exists, but with a different ledger",
+    ),
+    (
+        "TB_CREATE_ACCOUNT_EXISTS_WITH_DIFFERENT_CODE",
+        "An account with the same ID already exists, but with a different code",
+    ),
+    (
+        "TB_CREATE_ACCOUNT_EXISTS",
+        "An account with the same ID and the same fields (except the ones set by the cluster) already exists",
+    ),
+    (
+        "TB_CREATE_ACCOUNT_FLAGS_ARE_MUTUALLY_EXCLUSIVE",
+        "The account was not created, because it has both `debits_must_not_exceed_credits` and `credits_must_not_exceed_debits` flags set, which are mutually exclusive",
+    ),
+    (
+        "TB_CREATE_ACCOUNT_DEBITS_PENDING_MUST_BE_ZERO",
+        "The account was not created, because its `debits_pending` is not zero, while balances are only modified by transfers",
+    ),
+    (
+        "TB_CREATE_ACCOUNT_DEBITS_POSTED_MUST_BE_ZERO",
+        "The account was not created, because its `debits_posted` is not zero, while balances are only modified by transfers",
+    ),
+    (
+        "TB_CREATE_ACCOUNT_CREDITS_PENDING_MUST_BE_ZERO",
+        "The account was not created, because its `credits_pending` is not zero, while balances are only modified by transfers",
+    ),
+    (
+        "TB_CREATE_ACCOUNT_CREDITS_POSTED_MUST_BE_ZERO",
+        "The account was not created, because its `credits_posted` is not zero, while balances are only modified by transfers",
+    ),
+    (
+        "TB_CREATE_ACCOUNT_LEDGER_MUST_NOT_BE_ZERO",
+        "The account was not created, because its ledger is zero",
+    ),
+    (
+        "TB_CREATE_ACCOUNT_CODE_MUST_NOT_BE_ZERO",
+        "The account was not created, because its code is zero",
+    ),
+    (
+        "TB_CREATE_ACCOUNT_IMPORTED_EVENT_TIMESTAMP_MUST_NOT_REGRESS",
+        "The imported account was not created, because its timestamp is not greater than the timestamp of the latest account, or equals to the timestamp of an existing transfer",
+    ),
+    (
+        "TB_CREATE_TRANSFER_LINKED_EVENT_FAILED",
+        "The transfer was not created, because another transfer in its linked chain failed",
+    ),
+    (
+        "TB_CREATE_TRANSFER_LINKED_EVENT_CHAIN_OPEN",
+        "The transfer was not created, because it's the last one in the batch and has the `linked` flag set, leaving the chain open",
+    ),
+    (
+        "TB_CREATE_TRANSFER_IMPORTED_EVENT_EXPECTED",
+        "The transfer was not created, because the first transfer of the batch is imported, so all of them must have the `imported` flag set",
+    ),
+    (
+        "TB_CREATE_TRANSFER_IMPORTED_EVENT_NOT_EXPECTED",
+        "The transfer was not created, because the first transfer of the batch is not imported, so none of them may have the `imported` flag set",
+    ),
+    (
+        "TB_CREATE_TRANSFER_TIMESTAMP_MUST_BE_ZERO",
+        "The transfer was not created, because its timestamp is not zero, while it's set by the cluster for transfers not imported",
+    ),
+    (
+        "TB_CREATE_TRANSFER_IMPORTED_EVENT_TIMESTAMP_OUT_OF_RANGE",
+        "The imported transfer was not created, because its timestamp is out of range, while it must be greater than zero and less than 2^63",
+    ),
+    (
+        "TB_CREATE_TRANSFER_IMPORTED_EVENT_TIMESTAMP_MUST_NOT_ADVANCE",
+        "The imported transfer was not created, because its timestamp is greater than the current cluster time, while it must be a past one",
+    ),
+    (
+        "TB_CREATE_TRANSFER_RESERVED_FLAG",
+        "The transfer was not created, because one of its reserved flags is set",
+    ),
+    (
+        "TB_CREATE_TRANSFER_ID_MUST_NOT_BE_ZERO",
+        "The transfer was not created, because its ID is zero, which is a reserved value",
+    ),
+    (
+        "TB_CREATE_TRANSFER_ID_MUST_NOT_BE_INT_MAX",
+        "The transfer was not created, because its ID is 2^128 - 1, which is a reserved value",
+    ),
+    (
+        "TB_CREATE_TRANSFER_EXISTS_WITH_DIFFERENT_FLAGS",
+        "A transfer with the same ID already exists, but with different flags",
+    ),
+    (
+        "TB_CREATE_TRANSFER_EXISTS_WITH_DIFFERENT_PENDING_ID",
+        "A transfer with the same ID already exists, but with a different pending ID",
+    ),
+    (
+        "TB_CREATE_TRANSFER_EXISTS_WITH_DIFFERENT_TIMEOUT",
+        "A transfer with the same ID already exists, but with a different timeout",
+    ),
+    (
+        "TB_CREATE_TRANSFER_EXISTS_WITH_DIFFERENT_DEBIT_ACCOUNT_ID",
+        "A transfer with the same ID already exists, but with a different debit account ID",
+    ),
+    (
+        "TB_CREATE_TRANSFER_EXISTS_WITH_DIFFERENT_CREDIT_ACCOUNT_ID",
+        "A transfer with the same ID already exists, but with a different credit account ID",
+    ),
+    (
+        "TB_CREATE_TRANSFER_EXISTS_WITH_DIFFERENT_AMOUNT",
+        "A transfer with the same ID already exists, but with a different amount",
+    ),
+    (
+        "TB_CREATE_TRANSFER_EXISTS_WITH_DIFFERENT_USER_DATA_128",
+        "A transfer with the same ID already exists, but with a different `user_data_128`",
+    ),
+    (
+        "TB_CREATE_TRANSFER_EXISTS_WITH_DIFFERENT_USER_DATA_64",
+        "A transfer with the same ID already exists, but with a different `user_data_64`",
+    ),
+    (
+        "TB_CREATE_TRANSFER_EXISTS_WITH_DIFFERENT_USER_DATA_32",
+        "A transfer with the same ID already exists, but with a different `user_data_32`",
+    ),
+    (
+        "TB_CREATE_TRANSFER_EXISTS_WITH_DIFFERENT_LEDGER",
+        "A transfer with the same ID already exists, but with a different ledger",
+    ),
+    (
+        "TB_CREATE_TRANSFER_EXISTS_WITH_DIFFERENT_CODE",
+        "A transfer with the same ID already exists, but with a different code",
+    ),
+    (
+        "TB_CREATE_TRANSFER_EXISTS",
+        "A transfer with the same ID and the same fields (except the ones set by the cluster) already exists",
+    ),
+    (
+        "TB_CREATE_TRANSFER_ID_ALREADY_FAILED",
+        "The transfer was not created, because a transfer with the same ID has already failed with a transient error, so the ID cannot be reused",
+    ),
+    (
+        "TB_CREATE_TRANSFER_FLAGS_ARE_MUTUALLY_EXCLUSIVE",
+        "The transfer was not created, because it has a combination of flags which are mutually exclusive",
+    ),
+    (
+        "TB_CREATE_TRANSFER_DEBIT_ACCOUNT_ID_MUST_NOT_BE_ZERO",
+        "The transfer was not created, because its debit account ID is zero, which is a reserved value",
+    ),
+    (
+        "TB_CREATE_TRANSFER_DEBIT_ACCOUNT_ID_MUST_NOT_BE_INT_MAX",
+        "The transfer was not created, because its debit account ID is 2^128 - 1, which is a reserved value",
+    ),
+    (
+        "TB_CREATE_TRANSFER_CREDIT_ACCOUNT_ID_MUST_NOT_BE_ZERO",
+        "The transfer was not created, because its credit account ID is zero, which is a reserved value",
+    ),
+    (
+        "TB_CREATE_TRANSFER_CREDIT_ACCOUNT_ID_MUST_NOT_BE_INT_MAX",
+        "The transfer was not created, because its credit account ID is 2^128 - 1, which is a reserved value",
+    ),
+    (
+        "TB_CREATE_TRANSFER_ACCOUNTS_MUST_BE_DIFFERENT",
+        "The transfer was not created, because its debit and credit accounts are the same one",
+    ),
+    (
+        "TB_CREATE_TRANSFER_PENDING_ID_MUST_BE_ZERO",
+        "The transfer was not created, because it has a pending ID, while it doesn't post or void a pending transfer",
+    ),
+    (
+        "TB_CREATE_TRANSFER_PENDING_ID_MUST_NOT_BE_ZERO",
+        "The transfer was not created, because its pending ID is zero, while it posts or voids a pending transfer",
+    ),
+    (
+        "TB_CREATE_TRANSFER_PENDING_ID_MUST_NOT_BE_INT_MAX",
+        "The transfer was not created, because its pending ID is 2^128 - 1, which is a reserved value",
+    ),
+    (
+        "TB_CREATE_TRANSFER_PENDING_ID_MUST_BE_DIFFERENT",
+        "The transfer was not created, because its pending ID equals to its own ID",
+    ),
+    (
+        "TB_CREATE_TRANSFER_TIMEOUT_RESERVED_FOR_PENDING_TRANSFER",
+        "The transfer was not created, because it has a timeout, while it's not pending",
+    ),
+    (
+        "TB_CREATE_TRANSFER_CLOSING_TRANSFER_MUST_BE_PENDING",
+        "The transfer was not created, because it closes an account, while it's not pending",
+    ),
+    (
+        "TB_CREATE_TRANSFER_AMOUNT_MUST_NOT_BE_ZERO",
+        "The transfer was not created, because its amount is zero",
+    ),
+    (
+        "TB_CREATE_TRANSFER_LEDGER_MUST_NOT_BE_ZERO",
+        "The transfer was not created, because its ledger is zero",
+    ),
+    (
+        "TB_CREATE_TRANSFER_CODE_MUST_NOT_BE_ZERO",
+        "The transfer was not created, because its code is zero",
+    ),
+    (
+        "TB_CREATE_TRANSFER_DEBIT_ACCOUNT_NOT_FOUND",
+        "The transfer was not created, because its debit account doesn't exist",
+    ),
+    (
+        "TB_CREATE_TRANSFER_CREDIT_ACCOUNT_NOT_FOUND",
+        "The transfer was not created, because its credit account doesn't exist",
+    ),
+    (
+        "TB_CREATE_TRANSFER_ACCOUNTS_MUST_HAVE_THE_SAME_LEDGER",
+        "The transfer was not created, because its debit and credit accounts have different ledgers",
+    ),
+    (
+        "TB_CREATE_TRANSFER_TRANSFER_MUST_HAVE_THE_SAME_LEDGER_AS_ACCOUNTS",
+        "The transfer was not created, because its ledger differs from the one of its accounts",
+    ),
+    (
+        "TB_CREATE_TRANSFER_PENDING_TRANSFER_NOT_FOUND",
+        "The transfer was not created, because the pending transfer it posts or voids doesn't exist",
+    ),
+    (
+        "TB_CREATE_TRANSFER_PENDING_TRANSFER_NOT_PENDING",
+        "The transfer was not created, because the transfer it posts or voids is not pending",
+    ),
+    (
+        "TB_CREATE_TRANSFER_PENDING_TRANSFER_HAS_DIFFERENT_DEBIT_ACCOUNT_ID",
+        "The transfer was not created, because its debit account ID differs from the one of the pending transfer",
+    ),
+    (
+        "TB_CREATE_TRANSFER_PENDING_TRANSFER_HAS_DIFFERENT_CREDIT_ACCOUNT_ID",
+        "The transfer was not created, because its credit account ID differs from the one of the pending transfer",
+    ),
+    (
+        "TB_CREATE_TRANSFER_PENDING_TRANSFER_HAS_DIFFERENT_LEDGER",
+        "The transfer was not created, because its ledger differs from the one of the pending transfer",
+    ),
+    (
+        "TB_CREATE_TRANSFER_PENDING_TRANSFER_HAS_DIFFERENT_CODE",
+        "The transfer was not created, because its code differs from the one of the pending transfer",
+    ),
+    (
+        "TB_CREATE_TRANSFER_EXCEEDS_PENDING_TRANSFER_AMOUNT",
+        "The transfer was not created, because its amount is greater than the amount of the pending transfer",
+    ),
+    (
+        "TB_CREATE_TRANSFER_PENDING_TRANSFER_HAS_DIFFERENT_AMOUNT",
+        "The transfer was not created, because it voids the pending transfer with an amount different from the pending one",
+    ),
+    (
+        "TB_CREATE_TRANSFER_PENDING_TRANSFER_ALREADY_POSTED",
+        "The transfer was not created, because the pending transfer is already posted",
+    ),
+    (
+        "TB_CREATE_TRANSFER_PENDING_TRANSFER_ALREADY_VOIDED",
+        "The transfer was not created, because the pending transfer is already voided",
+    ),
+    (
+        "TB_CREATE_TRANSFER_PENDING_TRANSFER_EXPIRED",
+        "The transfer was not created, because the pending transfer has already expired",
+    ),
+    (
+        "TB_CREATE_TRANSFER_IMPORTED_EVENT_TIMESTAMP_MUST_NOT_REGRESS",
+        "The imported transfer was not created, because its timestamp is not greater than the timestamp of the latest transfer, or equals to the timestamp of an existing account",
+    ),
+    (
+        "TB_CREATE_TRANSFER_IMPORTED_EVENT_TIMESTAMP_MUST_POSTDATE_DEBIT_ACCOUNT",
+        "The imported transfer was not created, because its timestamp is not greater than the timestamp of its debit account",
+    ),
+    (
+        "TB_CREATE_TRANSFER_IMPORTED_EVENT_TIMESTAMP_MUST_POSTDATE_CREDIT_ACCOUNT",
+        "The imported transfer was not created, because its timestamp is not greater than the timestamp of its credit account",
+    ),
+    (
+        "TB_CREATE_TRANSFER_IMPORTED_EVENT_TIMEOUT_MUST_BE_ZERO",
+        "The imported transfer was not created, because it has a timeout, while imported pending transfers cannot expire",
+    ),
+    (
+        "TB_CREATE_TRANSFER_DEBIT_ACCOUNT_ALREADY_CLOSED",
+        "The transfer was not created, because its debit account is closed",
+    ),
+    (
+        "TB_CREATE_TRANSFER_CREDIT_ACCOUNT_ALREADY_CLOSED",
+        "The transfer was not created, because its credit account is closed",
+    ),
+    (
+        "TB_CREATE_TRANSFER_OVERFLOWS_DEBITS_PENDING",
+        "The transfer was not created, because it would overflow `debits_pending` of its debit account",
+    ),
+    (
+        "TB_CREATE_TRANSFER_OVERFLOWS_CREDITS_PENDING",
+        "The transfer was not created, because it would overflow `credits_pending` of its credit account",
+    ),
+    (
+        "TB_CREATE_TRANSFER_OVERFLOWS_DEBITS_POSTED",
+        "The transfer was not created, because it would overflow `debits_posted` of its debit account",
+    ),
+    (
+        "TB_CREATE_TRANSFER_OVERFLOWS_CREDITS_POSTED",
+        "The transfer was not created, because it would overflow `credits_posted` of its credit account",
+    ),
+    (
+        "TB_CREATE_TRANSFER_OVERFLOWS_DEBITS",
+        "The transfer was not created, because it would overflow the sum of `debits_pending` and `debits_posted` of its debit account",
+    ),
+    (
+        "TB_CREATE_TRANSFER_OVERFLOWS_CREDITS",
+        "The transfer was not created, because it would overflow the sum of `credits_pending` and `credits_posted` of its credit account",
+    ),
+    (
+        "TB_CREATE_TRANSFER_OVERFLOWS_TIMEOUT",
+        "The transfer was not created, because its timeout added to the cluster time would overflow a 64-bit timestamp",
+    ),
+    (
+        "TB_CREATE_TRANSFER_EXCEEDS_CREDITS",
+        "The transfer was not created, because its debit account has the `debits_must_not_exceed_credits` flag set, and its debits would exceed its credits",
+    ),
+    (
+        "TB_CREATE_TRANSFER_EXCEEDS_DEBITS",
+        "The transfer was not created, because its credit account has the `credits_must_not_exceed_debits` flag set, and its credits would exceed its debits",
+    ),
+];
+
 fn target_to_lib_dir(target: &str) -> Option<&'static str> {
     match target {
         "aarch64-unknown-linux-gnu" => Some("aarch64-linux-gnu.2.27"),
@@ -55,14 +560,54 @@ fn main() {
     );
     let target = env::var("TARGET").unwrap();
 
+    println!("cargo:rustc-env=TIGERBEETLE_RELEASE={TIGERBEETLE_RELEASE}");
+    println!("cargo:rustc-env=TIGERBEETLE_COMMIT={TIGERBEETLE_COMMIT}");
+
     println!("cargo:rerun-if-env-changed=DOCS_RS");
     println!("cargo:rerun-if-env-changed=TB_CLIENT_DEBUG");
+    println!("cargo:rerun-if-env-changed=TB_CLIENT_LIB_DIR");
     println!("cargo:rerun-if-env-changed=ZIG_PATH");
     println!("cargo:rerun-if-changed=src/wrapper.h");
 
     let wrapper;
     if env::var("DOCS_RS").is_ok() {
         wrapper = "src/wrapper.h".into();
+    } else if let Some(prebuilt_dir) = env::var_os("TB_CLIENT_LIB_DIR") {
+        // Link the prebuilt `tb_client` library instead of building it with Zig.
+        let prebuilt_dir = PathBuf::from(prebuilt_dir);
+        let lib_name = if target == "x86_64-pc-windows-gnu" {
+            "tb_client.lib"
+        } else {
+            "libtb_client.a"
+        };
+        let lib_path = prebuilt_dir.join(lib_name);
+        println!("cargo:rerun-if-changed={}", lib_path.display());
+        let lib = fs::read(&lib_path).unwrap_or_else(|e| {
+            panic!(
+                "`TB_CLIENT_LIB_DIR` is set to `{}` but reading `{lib_name}` failed: {e}",
+                prebuilt_dir.display(),
+            )
+        });
+        // The library is built with `-Dgit-commit`, so embeds the full commit hash, while the
+        // bare release string is too ambiguous to be matched reliably.
+        assert!(
+            contains(&lib, TIGERBEETLE_COMMIT.as_bytes()),
+            "prebuilt `{}` is not built from the {TIGERBEETLE_RELEASE} TigerBeetle release \
+             (commit {TIGERBEETLE_COMMIT})",
+            lib_path.display(),
+        );
+
+        let header = prebuilt_dir.join("tb_client.h");
+        println!("cargo:rerun-if-changed={}", header.display());
+        check_header(&header);
+
+        link_tb_client(&prebuilt_dir, &target);
+
+        let c_dir = out_dir.join("tb_client");
+        fs::create_dir_all(&c_dir).expect("creating `tb_client` headers directory");
+        fs::copy(&header, c_dir.join("tb_client.h")).expect("copying `tb_client.h`");
+        wrapper = c_dir.join("wrapper.h");
+        fs::copy("src/wrapper.h", &wrapper).expect("copying `wrapper.h`");
     } else {
         let target_lib_subdir = target_to_lib_dir(&target)
             .unwrap_or_else(|| panic!("target `{target:?}` is not supported"));
@@ -150,36 +695,10 @@ fn main() {
 
         let c_dir = tigerbeetle_root.join("src/clients/c/");
         let lib_dir = tigerbeetle_root.join("src/clients/c/lib");
-        let link_search = lib_dir.join(target_lib_subdir);
-        println!(
-            "cargo:rustc-link-search=native={}",
-            link_search
-                .to_str()
-                .expect("link search directory path is not valid unicode"),
-        );
-        if target == "x86_64-pc-windows-gnu" {
-            // `-gnu` toolchain looks for `lib<name>.a` file of a static library by default, but
-            // `zig build` produces `<name>.lib` despite using MinGW under-the-hood.
-            println!("cargo:rustc-link-lib=static:+verbatim=tb_client.lib");
-            // As of Rust 1.87, its `std` doesn't link `advapi32` automatically anymore, however
-            // the `tb_client` requires it.
-            // See: https://github.com/rust-lang/rust/pull/138233
-            //      https://github.com/rust-lang/rust/issues/139352
-            println!("cargo:rustc-link-lib=advapi32");
-        } else {
-            println!("cargo:rustc-link-lib=static=tb_client");
-        }
+        link_tb_client(&lib_dir.join(target_lib_subdir), &target);
 
         wrapper = c_dir.join("wrapper.h");
-        let generated_header = c_dir.join("tb_client.h");
-        assert_eq!(
-            fs::read_to_string(&generated_header).expect("reading generated `tb_client.h`"),
-            fs::read_to_string("src/tb_client.h")
-                .expect("reading pre-generated `tb_client.h`")
-                .replace("\r\n", "\n"),
-            "generated and pre-generated `tb_client.h` headers must be equal, \
-             generated at: {generated_header:?}",
-        );
+        check_header(&c_dir.join("tb_client.h"));
         fs::copy("src/wrapper.h", &wrapper).expect("copying `wrapper.h`");
     };
 
@@ -216,6 +735,118 @@ fn main() {
 
         rustfmt(generated_path);
     }
+
+    let mut visitor = AbiLayoutVisitor::default();
+    visitor.visit_file(&bindings);
+    let abi_path = out_dir.join("abi.rs");
+    fs::write(&abi_path, visitor.finish().to_string()).expect("writing ABI assertions");
+    rustfmt(abi_path);
+}
+
+/// Generator of compile-time assertions checking the bindings to conform the [`ABI_LAYOUT`].
+#[derive(Default)]
+struct AbiLayoutVisitor {
+    output: proc_macro2::TokenStream,
+    checked: Vec<String>,
+}
+
+impl AbiLayoutVisitor {
+    /// Returns the generated assertions.
+    ///
+    /// # Panics
+    ///
+    /// If any of the [`ABI_LAYOUT`] structs is missing in the bindings.
+    fn finish(self) -> proc_macro2::TokenStream {
+        for StructLayout { name, .. } in ABI_LAYOUT {
+            assert!(
+                self.checked.iter().any(|n| n == name),
+                "`{name}` struct is missing in `tb_client` bindings",
+            );
+        }
+        self.output
+    }
+}
+
+impl Visit<'_> for AbiLayoutVisitor {
+    fn visit_item_struct(&mut self, i: &syn::ItemStruct) {
+        let name = i.ident.to_string();
+        if let Some(StructLayout {
+            size,
+            align,
+            fields,
+            ..
+        }) = ABI_LAYOUT.iter().find(|l| l.name == name)
+        {
+            let actual = i
+                .fields
+                .iter()
+                .filter_map(|f| f.ident.as_ref().map(ToString::to_string))
+                .collect::<Vec<_>>();
+            let expected = fields.iter().map(|(n, _)| *n).collect::<Vec<_>>();
+            assert_eq!(
+                actual, expected,
+                "fields of `{name}` don't match the expected TigerBeetle ABI",
+            );
+
+            let ident = &i.ident;
+            let offsets = fields.iter().map(|(field, offset)| {
+                let field = syn::Ident::new(field, ident.span());
+                quote! {
+                    const _: () = assert!(::std::mem::offset_of!(#ident, #field) == #offset);
+                }
+            });
+            self.output.extend(quote! {
+                const _: () = assert!(::std::mem::size_of::<#ident>() == #size);
+                const _: () = assert!(::std::mem::align_of::<#ident>() == #align);
+                #(#offsets)*
+            });
+            self.checked.push(name);
+        }
+
+        syn::visit::visit_item_struct(self, i)
+    }
+}
+
+/// Instructs `rustc` to link the `tb_client` static library from the provided `link_search`
+/// directory.
+fn link_tb_client(link_search: &Path, target: &str) {
+    println!(
+        "cargo:rustc-link-search=native={}",
+        link_search
+            .to_str()
+            .expect("link search directory path is not valid unicode"),
+    );
+    if target == "x86_64-pc-windows-gnu" {
+        // `-gnu` toolchain looks for `lib<name>.a` file of a static library by default, but
+        // `zig build` produces `<name>.lib` despite using MinGW under-the-hood.
+        println!("cargo:rustc-link-lib=static:+verbatim=tb_client.lib");
+        // As of Rust 1.87, its `std` doesn't link `advapi32` automatically anymore, however
+        // the `tb_client` requires it.
+        // See: https://github.com/rust-lang/rust/pull/138233
+        //      https://github.com/rust-lang/rust/issues/139352
+        println!("cargo:rustc-link-lib=advapi32");
+    } else {
+        println!("cargo:rustc-link-lib=static=tb_client");
+    }
+}
+
+/// Checks the provided `tb_client.h` header to be equal to the pre-generated one.
+fn check_header(header: &Path) {
+    assert_eq!(
+        fs::read_to_string(header)
+            .expect("reading `tb_client.h`")
+            .replace("\r\n", "\n"),
+        fs::read_to_string("src/tb_client.h")
+            .expect("reading pre-generated `tb_client.h`")
+            .replace("\r\n", "\n"),
+        "provided and pre-generated `tb_client.h` headers must be equal, \
+         provided at: {header:?}",
+    );
+}
+
+/// Checks whether the provided `haystack` bytes contain the `needle` ones.
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|w| w == needle)
 }
 
 struct LintSuppressionVisitor;
@@ -306,6 +937,11 @@ impl Visit<'_> for TigerbeetleVisitor {
                     },
                     enum_ident.span(),
                 );
+                let snake_case_names = variants.iter().map(|(n, v, _)| {
+                    let s = n.to_lowercase();
+                    let n = syn::Ident::new(n, v.span());
+                    quote!((#s, Self:: #n))
+                });
                 let variants = variants.iter().map(|(n, v, _)| {
                     let n = syn::Ident::new(n, v.span());
                     quote!(const #n = super:: #enum_ident :: #v as #ty;)
@@ -317,6 +953,57 @@ impl Visit<'_> for TigerbeetleVisitor {
                             #(#variants)*
                         }
                     }
+
+                    impl #new_enum_ident {
+                        const SNAKE_CASE_NAMES: &'static [(&'static str, Self)] = &[
+                            #(#snake_case_names),*
+                        ];
+                    }
+
+                    impl ::std::fmt::Display for #new_enum_ident {
+                        #[doc = "Formats flags as their snake_case names joined by `|`, with any"]
+                        #[doc = "unknown bits formatted as a hexadecimal number."]
+                        fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                            let mut remaining = *self;
+                            let mut separator = "";
+                            for (name, flag) in Self::SNAKE_CASE_NAMES {
+                                if remaining.contains(*flag) {
+                                    write!(f, "{separator}{name}")?;
+                                    separator = "|";
+                                    remaining.remove(*flag);
+                                }
+                            }
+                            if !remaining.is_empty() {
+                                write!(f, "{separator}{:#x}", remaining.bits())?;
+                            }
+                            Ok(())
+                        }
+                    }
+
+                    impl ::std::str::FromStr for #new_enum_ident {
+                        type Err = ParseFlagsError;
+
+                        #[doc = "Parses flags from their snake_case names (or hexadecimal numbers)"]
+                        #[doc = "joined by `|`, as formatted by the `Display` implementation."]
+                        fn from_str(s: &str) -> Result<Self, Self::Err> {
+                            let mut flags = Self::empty();
+                            for name in s.split('|').map(str::trim).filter(|n| !n.is_empty()) {
+                                if let Some((_, flag)) =
+                                    Self::SNAKE_CASE_NAMES.iter().find(|(n, _)| *n == name)
+                                {
+                                    flags |= *flag;
+                                } else if let Some(bits) = name
+                                    .strip_prefix("0x")
+                                    .and_then(|hex| #ty::from_str_radix(hex, 16).ok())
+                                {
+                                    flags |= Self::from_bits_retain(bits);
+                                } else {
+                                    return Err(ParseFlagsError::new(name));
+                                }
+                            }
+                            Ok(flags)
+                        }
+                    }
                 })
             } else {
                 variants.iter_mut().for_each(|(n, _, _)| {
@@ -433,6 +1120,32 @@ impl Visit<'_> for TigerbeetleVisitor {
                         Self::UnstableUncategorized => unimplemented!("variant is not supported yet")
                     )));
 
+                let description_fn = enum_name.ends_with("_RESULT").then(|| {
+                    let branches = variants
+                        .iter()
+                        .map(|(s, v, _)| {
+                            let n = syn::Ident::new(s, v.span());
+                            let description = result_description(&v.to_string());
+                            quote!(Self:: #n => #description)
+                        })
+                        .chain(iter::once(quote!(
+                            Self::UnstableUncategorized => "Uncategorized"
+                        )));
+                    let first_doc_str = format!(
+                        "Returns a human-readable description of this [`{new_enum_name}`] variant"
+                    );
+                    quote! {
+                        #[doc = #first_doc_str]
+                        #[doc = ""]
+                        #[doc = "Adapted from the upstream reference docs."]
+                        pub fn description(self) -> &'static str {
+                            match self {
+                                #(#branches),*
+                            }
+                        }
+                    }
+                });
+
                 let variants = variants
                     .iter()
                     .map(|(n, v, _)| {
@@ -476,6 +1189,8 @@ impl Visit<'_> for TigerbeetleVisitor {
                                 #(#into_snake_case_str_branches),*
                             }
                         }
+
+                        #description_fn
                     }
                 });
                 self.output.extend(extra);
@@ -600,6 +1315,19 @@ impl bindgen::callbacks::ParseCallbacks for TigerbeetleCallbacks {
     }
 }
 
+/// Returns the description of the result code with the provided `name` from the
+/// [`RESULT_DESCRIPTIONS`].
+///
+/// # Panics
+///
+/// If the result code has no description.
+fn result_description(name: &str) -> &'static str {
+    RESULT_DESCRIPTIONS
+        .iter()
+        .find_map(|(n, d)| (*n == name).then_some(*d))
+        .unwrap_or_else(|| panic!("`{name}` result has no description in `RESULT_DESCRIPTIONS`"))
+}
+
 fn screaming_snake_case_into_camel_case(src: &str) -> String {
     let mut dst = String::with_capacity(src.len());
     for word in src.split('_') {
@@ -8,12 +8,50 @@
 
 include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
 
+/// Version of the [TigerBeetle] release the bundled `tb_client` library is built from.
+///
+/// [TigerBeetle]: https://tigerbeetle.com
+pub const TIGERBEETLE_RELEASE: &str = env!("TIGERBEETLE_RELEASE");
+
+/// Commit hash of the [`TIGERBEETLE_RELEASE`].
+pub const TIGERBEETLE_COMMIT: &str = env!("TIGERBEETLE_COMMIT");
+
+// Compile-time assertions of the bindings conforming the expected TigerBeetle ABI.
+include!(concat!(env!("OUT_DIR"), "/abi.rs"));
+
 /// Available only with `generated-safe` feature
 #[cfg(feature = "generated-safe")]
 #[allow(clippy::unnecessary_cast, clippy::assign_op_pattern)]
 #[doc(hidden)]
 pub mod generated_safe {
+    use std::{error::Error, fmt};
+
     include!(concat!(env!("OUT_DIR"), "/generated.rs"));
+
+    /// Error of parsing flags from a string, naming the unknown flag.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct ParseFlagsError {
+        name: String,
+    }
+
+    impl ParseFlagsError {
+        fn new(name: &str) -> Self {
+            Self { name: name.into() }
+        }
+
+        /// Returns the name of the unknown flag.
+        pub fn name(&self) -> &str {
+            &self.name
+        }
+    }
+
+    impl fmt::Display for ParseFlagsError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "Unknown flag: `{}`", self.name)
+        }
+    }
+
+    impl Error for ParseFlagsError {}
 }
 
 #[cfg(test)]
@@ -108,3 +146,25 @@ mod linked {
         }
     }
 }
+
+#[cfg(all(test, feature = "generated-safe"))]
+mod flags_spec {
+    use crate::generated_safe::TransferFlags;
+
+    #[test]
+    fn round_trips_through_string() {
+        let flags = TransferFlags::LINKED | TransferFlags::PENDING;
+
+        assert_eq!(flags.to_string(), "linked|pending");
+        assert_eq!("linked | pending".parse(), Ok(flags));
+        assert_eq!("".parse(), Ok(TransferFlags::empty()));
+    }
+
+    #[test]
+    fn names_unknown_flag() {
+        let err = "linked|bogus".parse::<TransferFlags>().unwrap_err();
+
+        assert_eq!(err.name(), "bogus");
+        assert_eq!(err.to_string(), "Unknown flag: `bogus`");
+    }
+}